@@ -0,0 +1,109 @@
+//! Transparent decompression of dump files.
+//!
+//! Each format other than [`Compression::None`] is decoded only when its cargo feature is enabled.
+
+use std::fmt;
+use std::io::{Cursor, Read};
+
+use anyhow::{bail, Context, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    /// Detects the compression format from the leading bytes of a stream.
+    pub fn detect(head: &[u8]) -> Compression {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else if head.starts_with(b"BZh") {
+            Compression::Bzip2
+        } else if head.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Compression::Xz
+        } else {
+            Compression::None
+        }
+    }
+
+    pub fn feature_name(self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gzip"),
+            Compression::Zstd => Some("zstd"),
+            Compression::Bzip2 => Some("bzip2"),
+            Compression::Xz => Some("xz"),
+        }
+    }
+
+    pub fn is_supported(self) -> bool {
+        match self {
+            Compression::None => true,
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+            Compression::Bzip2 => cfg!(feature = "bzip2"),
+            Compression::Xz => cfg!(feature = "xz"),
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Compression::None => "uncompressed",
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Bzip2 => "bzip2",
+            Compression::Xz => "xz",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Wraps `r` with a decoder chosen by sniffing its magic bytes.
+///
+/// The result is not buffered; the readers of [`crate::reader`] buffer it themselves.
+pub fn decompress<R: Read + Send + 'static>(mut r: R) -> Result<Box<dyn Read + Send>> {
+    let mut head = [0u8; 6];
+    let mut len = 0;
+    while len < head.len() {
+        match r.read(&mut head[len..]).context("reading magic bytes")? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    let compression = Compression::detect(&head[..len]);
+
+    if !compression.is_supported() {
+        bail!(
+            "{} compressed input is not supported: enable the `{}` feature",
+            compression,
+            compression.feature_name().unwrap_or_default()
+        );
+    }
+
+    let r = Cursor::new(head).take(len as u64).chain(r);
+    let r: Box<dyn Read + Send> = match compression {
+        Compression::None => Box::new(r),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(r)),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            Box::new(zstd::stream::read::Decoder::new(r).context("initializing zstd")?)
+        }
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(r)),
+        #[cfg(feature = "xz")]
+        Compression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(r)),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("unsupported compression is rejected above"),
+    };
+
+    Ok(r)
+}
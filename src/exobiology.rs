@@ -0,0 +1,153 @@
+//! Exobiology candidate prediction.
+//!
+//! Species are predicted from a table of [`SpeciesRule`]s loaded with [`RuleSet::from_json`].
+//! No table is bundled.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::from_slice;
+
+use crate::model::body::{
+    AtmosphereType, Body, Planet, PlanetSubType, Star, StarClass, VolcanismType,
+};
+use crate::system_tree::SystemTree;
+
+/// Conditions under which a species can occur.
+///
+/// Empty lists and missing ranges accept anything.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct SpeciesRule {
+    pub genus: String,
+    pub species: String,
+    /// Base value in credits.
+    pub value: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub atmospheres: Vec<AtmosphereType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body_types: Vec<PlanetSubType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volcanism: Vec<VolcanismType>,
+    /// Classes of the star the planet orbits.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub star_classes: Vec<StarClass>,
+    /// In g.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gravity: Option<Limits>,
+    /// In K.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<Limits>,
+    /// In atmospheres.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pressure: Option<Limits>,
+}
+
+/// Inclusive range.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Limits {
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+}
+
+impl Limits {
+    /// Returns `true` if `v` is in range. Unknown values are only in unbounded ranges.
+    pub fn contains(&self, v: Option<f32>) -> bool {
+        match v {
+            Some(v) => self.min.map_or(true, |m| m <= v) && self.max.map_or(true, |m| v <= m),
+            None => self.min.is_none() && self.max.is_none(),
+        }
+    }
+}
+
+impl SpeciesRule {
+    /// Returns `true` if the species can occur on `planet`, orbiting `star`.
+    ///
+    /// Only landable planets match. Rules with star classes do not match if `star` is unknown.
+    pub fn matches(&self, planet: &Planet, star: Option<&Star>) -> bool {
+        fn any_of<T: PartialEq>(list: &[T], v: Option<&T>) -> bool {
+            list.is_empty() || v.is_some_and(|v| list.contains(v))
+        }
+        let in_limits = |l: &Option<Limits>, v: Option<f32>| l.map_or(true, |l| l.contains(v));
+
+        planet.is_landable
+            && any_of(&self.atmospheres, planet.atmosphere_type.as_ref())
+            && any_of(&self.body_types, Some(&planet.sub_type))
+            && any_of(&self.volcanism, planet.volcanism_type.as_ref())
+            && any_of(
+                &self.star_classes,
+                star.and_then(|s| s.sub_type.filter_star_class()).as_ref(),
+            )
+            && in_limits(&self.gravity, planet.gravity)
+            && in_limits(&self.temperature, Some(planet.surface_temperature as f32))
+            && in_limits(&self.pressure, planet.surface_pressure)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Prediction<'a> {
+    pub genus: &'a str,
+    pub species: &'a str,
+    /// Base value in credits.
+    pub value: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSet {
+    pub rules: Vec<SpeciesRule>,
+}
+
+impl RuleSet {
+    /// Loads a table: a JSON array of [`SpeciesRule`]s.
+    pub fn from_json(bs: &[u8]) -> Result<RuleSet> {
+        let rules = from_slice(bs).context("parsing exobiology rules")?;
+        Ok(RuleSet { rules })
+    }
+
+    /// Species that can occur on `planet`, most valuable first.
+    pub fn predict(&self, planet: &Planet, star: Option<&Star>) -> Vec<Prediction<'_>> {
+        let mut found: Vec<_> = self
+            .rules
+            .iter()
+            .filter(|r| r.matches(planet, star))
+            .map(|r| Prediction {
+                genus: &r.genus,
+                species: &r.species,
+                value: r.value,
+            })
+            .collect();
+        found.sort_by(|a, b| b.value.cmp(&a.value).then(a.species.cmp(b.species)));
+        found
+    }
+
+    /// Like [`RuleSet::predict`], taking the star from the nearest star ancestor in `tree`.
+    pub fn predict_in_tree(&self, tree: &SystemTree, body_id: u64) -> Vec<Prediction<'_>> {
+        let Some(Body::Planet(planet)) = tree.get(body_id).and_then(|n| n.body.as_ref()) else {
+            return Vec::new();
+        };
+        let star = tree
+            .ancestors(body_id)
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|id| match tree.get(id)?.body.as_ref()? {
+                Body::Star(s) => Some(s),
+                _ => None,
+            });
+        self.predict(planet, star)
+    }
+}
+
+/// Value of the most valuable species of each genus, summed.
+///
+/// Only one species of a genus grows on a planet, so this is an upper bound of the planet's value.
+pub fn max_value(predictions: &[Prediction]) -> u64 {
+    let mut best: Vec<(&str, u64)> = Vec::new();
+    for p in predictions {
+        match best.iter_mut().find(|(g, _)| *g == p.genus) {
+            Some((_, v)) => *v = (*v).max(p.value),
+            None => best.push((p.genus, p.value)),
+        }
+    }
+    best.iter().map(|(_, v)| v).sum()
+}
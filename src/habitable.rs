@@ -0,0 +1,255 @@
+//! Habitable zones and the temperature bands of planet types.
+//!
+//! A zone is the range of distances at which a black body orbiting the star would be between two
+//! temperatures, `d = R / 2 * (T_star / T)²`, as used by the community's zone calculators.
+//! Albedo, atmospheres and other stars are ignored.
+
+use std::fmt;
+
+use crate::model::body::{Body, BodyT, PlanetSubType, Star, TerraformingState};
+use crate::physics::{Meters, Physical};
+use crate::system_tree::{NodeKind, SystemTree};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Zone {
+    /// Where terraformable and life-bearing planets are likely.
+    Habitable,
+    MetalRich,
+    EarthLike,
+    WaterWorld,
+    AmmoniaWorld,
+    Icy,
+}
+
+impl Zone {
+    pub const ALL: [Zone; 6] = [
+        Zone::Habitable,
+        Zone::MetalRich,
+        Zone::EarthLike,
+        Zone::WaterWorld,
+        Zone::AmmoniaWorld,
+        Zone::Icy,
+    ];
+
+    /// Black body temperatures in K at the inner and outer edges.
+    ///
+    /// `None` extends the zone to the star's surface or to infinity.
+    pub fn temperatures(self) -> (Option<f64>, Option<f64>) {
+        match self {
+            Zone::Habitable => (Some(315.0), Some(223.0)),
+            Zone::MetalRich => (None, Some(1100.0)),
+            Zone::EarthLike => (Some(278.0), Some(227.0)),
+            Zone::WaterWorld => (Some(307.0), Some(156.0)),
+            Zone::AmmoniaWorld => (Some(193.0), Some(117.0)),
+            Zone::Icy => (Some(150.0), None),
+        }
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Zone::Habitable => "Habitable zone",
+            Zone::MetalRich => "Metal-rich",
+            Zone::EarthLike => "Earth-like",
+            Zone::WaterWorld => "Water world",
+            Zone::AmmoniaWorld => "Ammonia world",
+            Zone::Icy => "Icy",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Extent of a zone around a star in light seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoneBounds {
+    pub zone: Zone,
+    pub inner: f64,
+    /// Infinite for zones without an outer edge.
+    pub outer: f64,
+}
+
+impl ZoneBounds {
+    pub fn contains(&self, ls: f64) -> bool {
+        self.inner <= ls && ls <= self.outer
+    }
+}
+
+/// Distance in light seconds at which a black body around `star` has `temperature`.
+pub fn black_body_distance(star: &Star, temperature: f64) -> f64 {
+    let ratio = star.surface_temperature as f64 / temperature;
+    Meters(star.radius().0 / 2.0 * ratio * ratio).in_ls()
+}
+
+/// Zones of `star`, or `None` if EDSM has no radius or temperature for it.
+pub fn zones(star: &Star) -> Option<Vec<ZoneBounds>> {
+    if star.solar_radius <= 0.0 || star.surface_temperature == 0 {
+        return None;
+    }
+    let surface = star.radius().in_ls();
+    let zones = Zone::ALL
+        .iter()
+        .map(|&zone| {
+            let (hot, cold) = zone.temperatures();
+            ZoneBounds {
+                zone,
+                inner: hot.map_or(surface, |t| black_body_distance(star, t)),
+                outer: cold.map_or(f64::INFINITY, |t| black_body_distance(star, t)),
+            }
+        })
+        .collect();
+    Some(zones)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarZones {
+    pub body_id: u64,
+    pub name: String,
+    pub zones: Vec<ZoneBounds>,
+}
+
+impl StarZones {
+    pub fn get(&self, zone: Zone) -> Option<&ZoneBounds> {
+        self.zones.iter().find(|z| z.zone == zone)
+    }
+}
+
+/// A planet and the zones of a star it orbits in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetZones {
+    pub body_id: u64,
+    pub name: String,
+    pub sub_type: PlanetSubType,
+    pub terraforming_state: Option<TerraformingState>,
+    /// Body ID of the star.
+    pub star: u64,
+    /// Distance to the star in light seconds.
+    pub distance: f64,
+    /// Zones the planet is inside, empty if none.
+    pub zones: Vec<Zone>,
+}
+
+impl PlanetZones {
+    pub fn is_in(&self, zone: Zone) -> bool {
+        self.zones.contains(&zone)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemZones {
+    pub stars: Vec<StarZones>,
+    /// Planets placed relative to a star with zones, in ascending order of body ID.
+    ///
+    /// A planet orbiting several stars appears once for each.
+    pub planets: Vec<PlanetZones>,
+    /// Body IDs of the planets that could not be placed relative to any star with zones, in
+    /// ascending order.
+    pub unplaced: Vec<u64>,
+}
+
+impl SystemZones {
+    /// Zones of the stars of `tree`, and the planets inside them.
+    ///
+    /// A planet is placed relative to the nearest star it orbits, directly or through its parent
+    /// planets, by the semi-major axis of the orbit around that star.
+    /// Barycentres have no orbit in the dumps, so a planet reaching the star through one, such as
+    /// a binary planet, is placed by the difference of their distances to arrival instead.
+    /// Planets orbiting a barycentre of stars are placed relative to each of those stars.
+    pub fn new(tree: &SystemTree) -> SystemZones {
+        let mut system = SystemZones::default();
+        for node in tree.nodes() {
+            let Some(Body::Star(star)) = &node.body else {
+                continue;
+            };
+            if let Some(zones) = zones(star) {
+                system.stars.push(StarZones {
+                    body_id: node.body_id,
+                    name: star.name.clone(),
+                    zones,
+                });
+            }
+        }
+
+        for node in tree.nodes() {
+            let Some(Body::Planet(planet)) = &node.body else {
+                continue;
+            };
+            let mut placed = false;
+            for (star, distance) in hosts(tree, node.body_id).unwrap_or_default() {
+                let Some(star_zones) = system.star(star) else {
+                    continue;
+                };
+                placed = true;
+                let zones = star_zones
+                    .zones
+                    .iter()
+                    .filter(|z| z.contains(distance))
+                    .map(|z| z.zone)
+                    .collect();
+                system.planets.push(PlanetZones {
+                    body_id: node.body_id,
+                    name: planet.name.clone(),
+                    sub_type: planet.sub_type.clone(),
+                    terraforming_state: planet.terraforming_state.clone(),
+                    star,
+                    distance,
+                    zones,
+                });
+            }
+            if !placed {
+                system.unplaced.push(node.body_id);
+            }
+        }
+        system
+    }
+
+    pub fn star(&self, body_id: u64) -> Option<&StarZones> {
+        self.stars.iter().find(|s| s.body_id == body_id)
+    }
+
+    /// Planets inside `zone`.
+    pub fn planets_in(&self, zone: Zone) -> impl Iterator<Item = &PlanetZones> {
+        self.planets.iter().filter(move |p| p.is_in(zone))
+    }
+}
+
+/// Stars that `body_id` orbits and its distance to each in light seconds.
+fn hosts(tree: &SystemTree, body_id: u64) -> Option<Vec<(u64, f64)>> {
+    let mut cur = body_id;
+    for _ in 0..tree.len() {
+        let parent = tree.parent(cur)?;
+        let stars: Vec<u64> = match parent.kind {
+            NodeKind::Star => vec![parent.body_id],
+            NodeKind::Barycentre => tree
+                .children(parent.body_id)
+                .filter(|n| n.kind == NodeKind::Star)
+                .map(|n| n.body_id)
+                .collect(),
+            NodeKind::Planet => Vec::new(),
+            NodeKind::Unknown => return None,
+        };
+        if !stars.is_empty() {
+            let orbit = tree
+                .get(cur)?
+                .body
+                .as_ref()
+                .and_then(|b| b.semi_major_axis());
+            if let Some(a) = orbit {
+                let distance = Meters::from_au(a as f64).in_ls();
+                return Some(stars.into_iter().map(|s| (s, distance)).collect());
+            }
+            // `cur` is a barycentre, or its orbit is missing.
+            let arrival = |id| {
+                let body = tree.get(id)?.body.as_ref()?;
+                body.distance_to_arrival().map(|d| d as f64)
+            };
+            let from = arrival(body_id)?;
+            return stars
+                .into_iter()
+                .map(|s| Some((s, (from - arrival(s)?).abs())))
+                .collect();
+        }
+        cur = parent.body_id;
+    }
+    None
+}
@@ -0,0 +1,423 @@
+//! Joins the dumps of one system by system address.
+//!
+//! [`SystemJoin`] takes the entries of any of the systems, bodies, stations, powerplay and
+//! populated systems dumps, and yields one [`SystemRecord`] per system address, in ascending
+//! order.
+//!
+//! Inputs that are already sorted by system address are merged as they are read with
+//! [`SystemJoin::sorted`].
+//! The nightly dumps are not, so [`SystemJoin::spilled`] first sorts them in runs that are
+//! written to disk, and then merges the runs, at most [`MAX_FAN_IN`] at a time.
+//!
+//! Entries without a system address are skipped.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, to_writer};
+
+use crate::model::body::{Body, BodyT};
+use crate::model::lenient::accepting_other;
+use crate::model::powerplay::PowerPlay;
+use crate::model::station::Station;
+use crate::model::system::{System, SystemWithCoordinates};
+use crate::model::system_populated::SystemPopulated;
+
+/// Everything known about one system.
+///
+/// If there are several system, powerplay or populated system entries for the system, the one
+/// from the later input, or later in the same input, is kept and the others are counted in
+/// `replaced`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemRecord {
+    pub id64: u64,
+    pub system: Option<SystemWithCoordinates>,
+    pub bodies: Vec<Body>,
+    pub stations: Vec<Station>,
+    pub powerplay: Option<PowerPlay>,
+    pub populated: Option<SystemPopulated>,
+    /// Number of entries replaced by a later one of the same kind.
+    pub replaced: usize,
+}
+
+impl SystemRecord {
+    fn new(id64: u64) -> SystemRecord {
+        SystemRecord {
+            id64,
+            system: None,
+            bodies: Vec::new(),
+            stations: Vec::new(),
+            powerplay: None,
+            populated: None,
+            replaced: 0,
+        }
+    }
+
+    /// Name of the system from whichever entry has one.
+    pub fn name(&self) -> Option<&str> {
+        self.system
+            .as_ref()
+            .map(|s| s.name.as_str())
+            .or_else(|| self.populated.as_ref().map(|s| s.name.as_str()))
+            .or_else(|| self.powerplay.as_ref().map(|s| s.name.as_str()))
+            .or_else(|| self.bodies.iter().find_map(|b| b.system_name()))
+            .or_else(|| self.stations.iter().find_map(|s| s.system_name.as_deref()))
+    }
+
+    /// Adds `entry`. Later entries replace earlier ones of the single-valued kinds.
+    fn add(&mut self, entry: Entry) {
+        let replaced = match entry {
+            Entry::System(s) => self.system.replace(s).is_some(),
+            Entry::Body(b) => {
+                self.bodies.push(b);
+                false
+            }
+            Entry::Station(s) => {
+                self.stations.push(s);
+                false
+            }
+            Entry::PowerPlay(p) => self.powerplay.replace(p).is_some(),
+            Entry::Populated(p) => self.populated.replace(p).is_some(),
+        };
+        if replaced {
+            self.replaced += 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+enum Entry {
+    System(SystemWithCoordinates),
+    Body(Body),
+    Station(Station),
+    PowerPlay(PowerPlay),
+    Populated(SystemPopulated),
+}
+
+impl Entry {
+    fn id64(&self) -> Option<u64> {
+        match self {
+            Entry::System(s) => s.id64(),
+            Entry::Body(b) => b.system_address().map(|a| a.0),
+            Entry::Station(s) => s.system_id64,
+            Entry::PowerPlay(p) => Some(p.id64),
+            Entry::Populated(p) => p.id64,
+        }
+    }
+}
+
+/// Most spill files merged at once. More runs are merged in passes.
+pub const MAX_FAN_IN: usize = 64;
+
+type Keyed = (u64, Entry);
+type Stream<'a> = Box<dyn Iterator<Item = Result<Keyed>> + 'a>;
+
+/// Inputs of a join, added with the builder methods.
+#[derive(Default)]
+pub struct SystemJoin<'a> {
+    sources: Vec<Stream<'a>>,
+}
+
+impl<'a> SystemJoin<'a> {
+    pub fn new() -> SystemJoin<'a> {
+        SystemJoin::default()
+    }
+
+    pub fn systems<I>(self, entries: I) -> SystemJoin<'a>
+    where
+        I: IntoIterator<Item = Result<SystemWithCoordinates>> + 'a,
+    {
+        self.source(entries, Entry::System)
+    }
+
+    pub fn bodies<I>(self, entries: I) -> SystemJoin<'a>
+    where
+        I: IntoIterator<Item = Result<Body>> + 'a,
+    {
+        self.source(entries, Entry::Body)
+    }
+
+    pub fn stations<I>(self, entries: I) -> SystemJoin<'a>
+    where
+        I: IntoIterator<Item = Result<Station>> + 'a,
+    {
+        self.source(entries, Entry::Station)
+    }
+
+    pub fn powerplay<I>(self, entries: I) -> SystemJoin<'a>
+    where
+        I: IntoIterator<Item = Result<PowerPlay>> + 'a,
+    {
+        self.source(entries, Entry::PowerPlay)
+    }
+
+    pub fn populated<I>(self, entries: I) -> SystemJoin<'a>
+    where
+        I: IntoIterator<Item = Result<SystemPopulated>> + 'a,
+    {
+        self.source(entries, Entry::Populated)
+    }
+
+    fn source<T, I, F>(mut self, entries: I, wrap: F) -> SystemJoin<'a>
+    where
+        I: IntoIterator<Item = Result<T>> + 'a,
+        F: Fn(T) -> Entry + 'a,
+    {
+        let keyed = entries.into_iter().filter_map(move |r| match r {
+            Ok(entry) => {
+                let entry = wrap(entry);
+                entry.id64().map(|id64| Ok((id64, entry)))
+            }
+            Err(e) => Some(Err(e)),
+        });
+        self.sources.push(Box::new(keyed));
+        self
+    }
+
+    /// Joins inputs that are each sorted by system address.
+    ///
+    /// The records fail with an error when an input turns out to be unsorted.
+    pub fn sorted(self) -> Records<'a> {
+        let streams = self
+            .sources
+            .into_iter()
+            .enumerate()
+            .map(|(i, source)| {
+                let mut last = None;
+                let checked = source.map(move |r| {
+                    let (id64, entry) = r?;
+                    if last > Some(id64) {
+                        bail!(
+                            "input {} is not sorted: {} after {}",
+                            i,
+                            id64,
+                            last.unwrap()
+                        );
+                    }
+                    last = Some(id64);
+                    Ok((id64, entry))
+                });
+                Box::new(checked) as Stream
+            })
+            .collect();
+        Records::new(Merge::new(streams), Runs(Vec::new()))
+    }
+
+    /// Joins inputs in any order.
+    ///
+    /// The inputs are read to the end first, and written to `dir` in sorted runs of `run_size`
+    /// entries. The run files are removed when the records are dropped.
+    pub fn spilled<P: AsRef<Path>>(self, dir: P, run_size: usize) -> Result<Records<'static>> {
+        let dir = dir.as_ref();
+        let run_size = run_size.max(1);
+        let mut runs = Runs(Vec::new());
+        let mut buf = Vec::with_capacity(run_size);
+        for source in self.sources {
+            for entry in source {
+                buf.push(entry?);
+                if buf.len() >= run_size {
+                    runs.write_buffer(dir, &mut buf)?;
+                }
+            }
+        }
+        if !buf.is_empty() {
+            runs.write_buffer(dir, &mut buf)?;
+        }
+
+        // Runs are merged in order, so that ties still go to the entry read first.
+        while runs.0.len() > MAX_FAN_IN {
+            let pass = Runs(std::mem::take(&mut runs.0));
+            for chunk in pass.0.chunks(MAX_FAN_IN) {
+                runs.write(dir, Merge::new(open_runs(chunk)?))?;
+            }
+        }
+
+        Ok(Records::new(Merge::new(open_runs(&runs.0)?), runs))
+    }
+}
+
+fn open_runs(paths: &[PathBuf]) -> Result<Vec<Stream<'static>>> {
+    let mut streams = Vec::with_capacity(paths.len());
+    for path in paths {
+        let f = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        let path = path.clone();
+        let lines = BufReader::new(f).lines().map(move |line| {
+            let line = line.with_context(|| format!("reading {}", path.display()))?;
+            read_run_line(&line).with_context(|| format!("parsing {}", path.display()))
+        });
+        streams.push(Box::new(lines) as Stream);
+    }
+    Ok(streams)
+}
+
+/// Parses a line of a spill file.
+///
+/// Entries may have been parsed leniently, so `Other` values are read back in lenient mode.
+fn read_run_line(line: &str) -> Result<Keyed> {
+    Ok(accepting_other(|| from_str(line))?)
+}
+
+/// Spill files, removed on drop.
+struct Runs(Vec<PathBuf>);
+
+impl Runs {
+    /// Sorts `buf` and writes it as a new run, leaving `buf` empty.
+    fn write_buffer(&mut self, dir: &Path, buf: &mut Vec<Keyed>) -> Result<()> {
+        buf.sort_by_key(|(id64, _)| *id64);
+        self.write(dir, buf.drain(..).map(Ok))
+    }
+
+    /// Writes sorted `entries` as a new run.
+    fn write<I: IntoIterator<Item = Result<Keyed>>>(
+        &mut self,
+        dir: &Path,
+        entries: I,
+    ) -> Result<()> {
+        static NEXT_RUN: AtomicUsize = AtomicUsize::new(0);
+        let path = dir.join(format!(
+            "edsm-join-{}-{}.jsonl",
+            std::process::id(),
+            NEXT_RUN.fetch_add(1, Ordering::Relaxed)
+        ));
+        let f = File::create(&path).with_context(|| format!("creating {}", path.display()))?;
+        self.0.push(path.clone());
+
+        let mut w = BufWriter::new(f);
+        for keyed in entries {
+            to_writer(&mut w, &keyed?).with_context(|| format!("writing {}", path.display()))?;
+            w.write_all(b"\n")?;
+        }
+        w.flush()
+            .with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+impl Drop for Runs {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// K-way merge of sorted streams. Ties go to the earlier stream.
+struct Merge<'a> {
+    streams: Vec<Stream<'a>>,
+    heads: Vec<Option<Entry>>,
+    heap: BinaryHeap<Reverse<(u64, usize)>>,
+    started: bool,
+    error: Option<anyhow::Error>,
+}
+
+impl<'a> Merge<'a> {
+    fn new(streams: Vec<Stream<'a>>) -> Merge<'a> {
+        let heads = streams.iter().map(|_| None).collect();
+        Merge {
+            streams,
+            heads,
+            heap: BinaryHeap::new(),
+            started: false,
+            error: None,
+        }
+    }
+
+    fn advance(&mut self, i: usize) {
+        match self.streams[i].next() {
+            Some(Ok((id64, entry))) => {
+                self.heads[i] = Some(entry);
+                self.heap.push(Reverse((id64, i)));
+            }
+            Some(Err(e)) => self.error = Some(e),
+            None => {}
+        }
+    }
+}
+
+impl Iterator for Merge<'_> {
+    type Item = Result<Keyed>;
+
+    fn next(&mut self) -> Option<Result<Keyed>> {
+        if !self.started {
+            self.started = true;
+            for i in 0..self.streams.len() {
+                self.advance(i);
+                if self.error.is_some() {
+                    break;
+                }
+            }
+        }
+        if let Some(e) = self.error.take() {
+            self.heap.clear();
+            return Some(Err(e));
+        }
+        let Reverse((id64, i)) = self.heap.pop()?;
+        let entry = self.heads[i].take().expect("merge head");
+        self.advance(i);
+        Some(Ok((id64, entry)))
+    }
+}
+
+/// Joined records, in ascending order of system address.
+pub struct Records<'a> {
+    merge: Merge<'a>,
+    next: Option<Keyed>,
+    done: bool,
+    // Dropped after `merge`, which holds the spill files open.
+    _runs: Runs,
+}
+
+impl<'a> Records<'a> {
+    fn new(merge: Merge<'a>, runs: Runs) -> Records<'a> {
+        Records {
+            merge,
+            next: None,
+            done: false,
+            _runs: runs,
+        }
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Result<SystemRecord>;
+
+    fn next(&mut self) -> Option<Result<SystemRecord>> {
+        if self.done {
+            return None;
+        }
+        let (id64, entry) = match self.next.take() {
+            Some(keyed) => keyed,
+            None => match self.merge.next()? {
+                Ok(keyed) => keyed,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            },
+        };
+
+        let mut record = SystemRecord::new(id64);
+        record.add(entry);
+        loop {
+            match self.merge.next() {
+                Some(Ok((next_id64, entry))) if next_id64 == id64 => record.add(entry),
+                Some(Ok(keyed)) => {
+                    self.next = Some(keyed);
+                    break;
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => break,
+            }
+        }
+        Some(Ok(record))
+    }
+}
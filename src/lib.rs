@@ -1 +1,16 @@
+pub mod compression;
+pub mod exobiology;
+pub mod habitable;
+pub mod join;
+pub mod materials;
 pub mod model;
+pub mod orbit;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod physics;
+pub mod reader;
+pub mod route;
+pub mod schema_report;
+pub mod spatial;
+pub mod system_tree;
+pub mod value;
@@ -0,0 +1,278 @@
+//! Search for raw materials on landable planets.
+//!
+//! The bodies dump has no coordinates, so [`MaterialFinder`] keeps the IDs and scores of matching
+//! planets while the bodies are read, and places them when the systems are read afterwards:
+//!
+//! ```no_run
+//! # use std::fs::File;
+//! # use anyhow::Result;
+//! # use edsm_dumps_model::materials::{Jumponium, MaterialFinder};
+//! # use edsm_dumps_model::model::body::Body;
+//! # use edsm_dumps_model::model::system::{Coords, SystemWithCoordinates};
+//! # use edsm_dumps_model::reader::DumpReader;
+//! # fn main() -> Result<()> {
+//! let sol = Coords { x: 0.0, y: 0.0, z: 0.0 };
+//! let mut finder = MaterialFinder::new(Jumponium::Premium.materials(), sol);
+//! for body in DumpReader::<Body, _>::new(File::open("bodies.json")?) {
+//!     finder.add_body(&body?);
+//! }
+//! for system in DumpReader::<SystemWithCoordinates, _>::new(File::open("systemsWithCoordinates.json")?) {
+//!     finder.add_system(&system?);
+//! }
+//! for deposit in finder.deposits().iter().take(10) {
+//!     println!("{} {:.1} ly {:.1}%", deposit.name, deposit.distance, deposit.percent);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::model::body::{Body, MaterialsKey, Planet};
+use crate::model::system::{Coords, HasCoords, System};
+
+/// Materials for the FSD injection syntheses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Jumponium {
+    /// +25% jump range.
+    Basic,
+    /// +50% jump range.
+    Standard,
+    /// +100% jump range.
+    Premium,
+}
+
+impl Jumponium {
+    pub fn materials(self) -> &'static [MaterialsKey] {
+        match self {
+            Jumponium::Basic => &[
+                MaterialsKey::Carbon,
+                MaterialsKey::Vanadium,
+                MaterialsKey::Germanium,
+            ],
+            Jumponium::Standard => &[
+                MaterialsKey::Carbon,
+                MaterialsKey::Vanadium,
+                MaterialsKey::Germanium,
+                MaterialsKey::Cadmium,
+                MaterialsKey::Niobium,
+            ],
+            Jumponium::Premium => &[
+                MaterialsKey::Carbon,
+                MaterialsKey::Germanium,
+                MaterialsKey::Arsenic,
+                MaterialsKey::Niobium,
+                MaterialsKey::Yttrium,
+                MaterialsKey::Polonium,
+            ],
+        }
+    }
+}
+
+/// Which planets match a set of materials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Match {
+    /// Planets with all of the materials, scored by the scarcest one.
+    #[default]
+    All,
+    /// Planets with any of the materials, scored by the most abundant one.
+    Any,
+}
+
+/// How deposits are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortBy {
+    /// Highest percentage first, then nearest.
+    #[default]
+    Percent,
+    /// Nearest first, then highest percentage.
+    Distance,
+}
+
+/// A planet with the materials, placed in its system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deposit {
+    /// EDSM ID of the planet.
+    pub body_id: u64,
+    pub name: String,
+    pub coords: Coords,
+    /// To the reference point in ly.
+    pub distance: f32,
+    /// Score of the planet, see [`Match`].
+    pub percent: f32,
+}
+
+/// A matching planet whose system was not added yet.
+#[derive(Debug, Clone)]
+struct Pending {
+    body_id: u64,
+    name: String,
+    percent: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MaterialFinder {
+    materials: Vec<MaterialsKey>,
+    reference: Coords,
+    mode: Match,
+    min_percent: f32,
+    max_distance: Option<f32>,
+    pending: Vec<Option<Pending>>,
+    by_id64: HashMap<u64, Vec<usize>>,
+    by_id: HashMap<u64, Vec<usize>>,
+    deposits: Vec<Deposit>,
+}
+
+impl MaterialFinder {
+    /// Finder for planets with all of `materials`, measuring distances from `reference`.
+    pub fn new<I, K>(materials: I, reference: Coords) -> MaterialFinder
+    where
+        I: IntoIterator<Item = K>,
+        K: Borrow<MaterialsKey>,
+    {
+        let mut materials: Vec<_> = materials.into_iter().map(|k| k.borrow().clone()).collect();
+        materials.sort_unstable();
+        materials.dedup();
+        MaterialFinder {
+            materials,
+            reference,
+            mode: Match::All,
+            min_percent: 0.0,
+            max_distance: None,
+            pending: Vec::new(),
+            by_id64: HashMap::new(),
+            by_id: HashMap::new(),
+            deposits: Vec::new(),
+        }
+    }
+
+    pub fn matching(mut self, mode: Match) -> MaterialFinder {
+        self.mode = mode;
+        self
+    }
+
+    /// Ignores planets scoring below `percent`.
+    pub fn min_percent(mut self, percent: f32) -> MaterialFinder {
+        self.min_percent = percent;
+        self
+    }
+
+    /// Ignores planets farther than `ly` from the reference point.
+    pub fn max_distance(mut self, ly: f32) -> MaterialFinder {
+        self.max_distance = Some(ly);
+        self
+    }
+
+    pub fn materials(&self) -> &[MaterialsKey] {
+        &self.materials
+    }
+
+    /// Score of `planet`, or `None` if it does not match.
+    pub fn score(&self, planet: &Planet) -> Option<f32> {
+        if !planet.is_landable || self.materials.is_empty() {
+            return None;
+        }
+        let materials = planet.materials.as_ref()?;
+        let found = self.materials.iter().map(|k| materials.get(k.clone()));
+        let score = match self.mode {
+            Match::All => found
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .reduce(f32::min),
+            Match::Any => found.flatten().reduce(f32::max),
+        }?;
+        (score >= self.min_percent).then_some(score)
+    }
+
+    /// Keeps the ID and score of `body` if it is a matching planet, until its system is added.
+    ///
+    /// Returns `true` if the body was kept.
+    pub fn add_body(&mut self, body: &Body) -> bool {
+        let Body::Planet(planet) = body else {
+            return false;
+        };
+        let Some(score) = self.score(planet) else {
+            return false;
+        };
+        let i = self.pending.len();
+        match (planet.system_id64, planet.system_id) {
+            (Some(id64), _) => self.by_id64.entry(id64).or_default().push(i),
+            (None, Some(id)) => self.by_id.entry(id).or_default().push(i),
+            (None, None) => return false,
+        }
+        self.pending.push(Some(Pending {
+            body_id: planet.id,
+            name: planet.name.clone(),
+            percent: score,
+        }));
+        true
+    }
+
+    /// Places the kept planets of `system`.
+    ///
+    /// Returns the number of deposits placed.
+    pub fn add_system<S: System + HasCoords>(&mut self, system: &S) -> usize {
+        let mut found = self.by_id.remove(&system.id()).unwrap_or_default();
+        if let Some(id64) = system.id64() {
+            found.extend(self.by_id64.remove(&id64).unwrap_or_default());
+        }
+        if found.is_empty() {
+            return 0;
+        }
+
+        let coords = system.coords();
+        let distance = coords.dist(self.reference);
+        let in_range = self.max_distance.map_or(true, |max| distance <= max);
+        let mut placed = 0;
+        for i in found {
+            let Some(pending) = self.pending[i].take() else {
+                continue;
+            };
+            if in_range {
+                self.deposits.push(Deposit {
+                    body_id: pending.body_id,
+                    name: pending.name,
+                    coords,
+                    distance,
+                    percent: pending.percent,
+                });
+                placed += 1;
+            }
+        }
+        placed
+    }
+
+    /// Number of kept planets whose system was not added yet.
+    pub fn unplaced(&self) -> usize {
+        self.by_id64
+            .values()
+            .chain(self.by_id.values())
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Placed deposits, highest percentage first.
+    pub fn deposits(&mut self) -> &[Deposit] {
+        self.sorted(SortBy::Percent)
+    }
+
+    pub fn sorted(&mut self, by: SortBy) -> &[Deposit] {
+        let by_percent = |a: &Deposit, b: &Deposit| b.percent.total_cmp(&a.percent);
+        let by_distance = |a: &Deposit, b: &Deposit| a.distance.total_cmp(&b.distance);
+        self.deposits.sort_by(|a, b| {
+            let order: Ordering = match by {
+                SortBy::Percent => by_percent(a, b).then(by_distance(a, b)),
+                SortBy::Distance => by_distance(a, b).then(by_percent(a, b)),
+            };
+            order.then(a.body_id.cmp(&b.body_id))
+        });
+        &self.deposits
+    }
+
+    pub fn into_deposits(mut self, by: SortBy) -> Vec<Deposit> {
+        self.sorted(by);
+        self.deposits
+    }
+}
@@ -2,6 +2,8 @@
 
 pub mod bgs;
 pub mod body;
+pub mod codex;
+pub mod lenient;
 pub mod powerplay;
 pub mod station;
 pub mod system;
@@ -19,6 +21,8 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::from_slice;
 
+use lenient::{parse_lenient_or, Lenient};
+
 pub trait RootEntry: 'static + Send + Sync + DeserializeOwned + Serialize {
     fn entry_id(&self) -> u64;
     fn type_name() -> &'static str;
@@ -28,6 +32,19 @@ pub trait RootEntry: 'static + Send + Sync + DeserializeOwned + Serialize {
         from_slice(bs).context("parsing entry")
     }
 
+    /// Parses an entry, removing unknown fields and loading unknown enum values as `Other`.
+    ///
+    /// See [`lenient`] for details.
+    fn parse_dump_json_lenient(bs: &[u8]) -> Result<Lenient<Self>> {
+        Self::scan_dump_json(bs).transpose()
+    }
+
+    /// Like [`RootEntry::parse_dump_json_lenient`], but keeps the unknown fields and enum values
+    /// found in an entry that cannot be parsed even leniently.
+    fn scan_dump_json(bs: &[u8]) -> Lenient<Result<Self>> {
+        parse_lenient_or(bs, |_| None)
+    }
+
     #[deprecated = "use parse_dump_json()"]
     fn pre_filter(s: &str) -> Cow<'_, str> {
         Cow::Borrowed(s)
@@ -3,7 +3,7 @@ use strum::EnumIter;
 use variant_count::VariantCount;
 
 use super::util::DisplayViaSerde;
-use crate::display_via_serde;
+use crate::{display_via_serde, serde_with_other};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -16,6 +16,7 @@ pub struct ActiveState {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Allegiance {
     Alliance,
     Empire,
@@ -24,9 +25,14 @@ pub enum Allegiance {
     #[serde(rename = "Pilots Federation")]
     PilotsFederation,
     Thargoid,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(Allegiance);
+serde_with_other!(Allegiance);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -44,6 +50,7 @@ pub struct ControllingFaction {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Economy {
     None,
     Agriculture,
@@ -64,13 +71,19 @@ pub enum Economy {
     Terraforming,
     Tourism,
     Engineer,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(Economy);
+serde_with_other!(Economy);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Government {
     None,
     Anarchy,
@@ -90,9 +103,14 @@ pub enum Government {
     WorkshopEngineer,
     #[serde(rename = "Fleet Carrier")]
     FleetCarrier,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(Government);
+serde_with_other!(Government);
 
 #[derive(
     Debug,
@@ -109,6 +127,7 @@ display_via_serde!(Government);
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Happiness {
     Despondent,
     Unhappy,
@@ -116,9 +135,14 @@ pub enum Happiness {
     None,
     Happy,
     Elated,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(Happiness);
+serde_with_other!(Happiness);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -153,18 +177,25 @@ pub struct RecoveringState {
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Security {
     Anarchy,
     Low,
     Medium,
     High,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(Security);
+serde_with_other!(Security);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum State {
     Blight,
     Boom,
@@ -195,6 +226,11 @@ pub enum State {
     #[serde(rename = "Terrorist Attack")]
     TerroristAttack,
     War,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(State);
+serde_with_other!(State);
@@ -1,19 +1,22 @@
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::sync::OnceLock;
 
-use anyhow::{Error, Result};
+use anyhow::{bail, Error, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::from_slice;
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
 use variant_count::VariantCount;
 
 use super::dec::date_format;
+use super::lenient::{parse_lenient_or, Lenient};
+use super::system::Id64;
 use super::RootEntry;
 
 use super::util::DisplayViaSerde;
-use crate::display_via_serde;
+use crate::{display_via_serde, serde_with_other};
 
 // Main Type
 
@@ -37,6 +40,50 @@ pub trait BodyT {
     fn rotational_period_tidally_locked(&self) -> Option<bool>;
     fn semi_major_axis(&self) -> Option<f32>;
     fn surface_temperature(&self) -> Option<u64>;
+
+    fn address(&self) -> Option<Id64> {
+        self.id64().map(Id64)
+    }
+
+    /// Address of the system, from `system_id64` or else from `id64`.
+    fn system_address(&self) -> Option<Id64> {
+        self.system_id64()
+            .map(Id64)
+            .or_else(|| self.address().map(Id64::system))
+    }
+
+    /// Splits `id64` into the system address and the body ID.
+    fn split_id64(&self) -> Option<(Id64, u64)> {
+        self.address().map(|a| (a.system(), a.body_id()))
+    }
+
+    /// Checks that `id64` agrees with `system_id64` and `body_id` where they are present.
+    fn validate_id64(&self) -> Result<()> {
+        let Some((system, body_id)) = self.split_id64() else {
+            return Ok(());
+        };
+        if let Some(system_id64) = self.system_id64() {
+            if system.0 != system_id64 {
+                bail!(
+                    "id64 {} is in system {}, but systemId64 is {}",
+                    self.id64().unwrap_or_default(),
+                    system,
+                    system_id64
+                );
+            }
+        }
+        if let Some(expected) = self.body_id() {
+            if body_id != expected {
+                bail!(
+                    "id64 {} has body ID {}, but bodyId is {}",
+                    self.id64().unwrap_or_default(),
+                    body_id,
+                    expected
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 macro_rules! deref_impl {
@@ -142,6 +189,10 @@ impl RootEntry for Body {
         }
     }
 
+    fn scan_dump_json(bs: &[u8]) -> Lenient<Result<Self>> {
+        parse_lenient_or(bs, |bs| from_slice(bs).ok().map(Body::Unknown))
+    }
+
     fn pre_filter(s: &str) -> Cow<'_, str> {
         let null_pos = s.find(r#""type":null"#);
         let first_compound = match (s.find(":{"), s.find('[')) {
@@ -394,6 +445,26 @@ pub struct Star {
     pub update_time: DateTime<Utc>,
 }
 
+impl Star {
+    /// Spectral and luminosity class, if the spectral class is known.
+    pub fn classification(&self) -> Option<StellarClassification> {
+        Some(StellarClassification {
+            class: self.spectral_class.clone()?,
+            luminosity: self.luminosity.clone(),
+        })
+    }
+
+    /// Checks that the spectral class agrees with `sub_type`.
+    pub fn validate_classification(&self) -> Result<()> {
+        match self.classification() {
+            Some(c) if !c.is_consistent_with(&self.sub_type) => {
+                bail!("spectral class {} is not of class {}", c, self.sub_type)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 impl BodyT for Star {
     body_t_impl!(id, u64);
     body_t_impl!(id64, Option<u64>);
@@ -464,7 +535,6 @@ impl BodyT for Unknown {
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -477,15 +547,21 @@ impl BodyT for Unknown {
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum AsteroidType {
     Icy,
     Rocky,
     #[serde(rename = "Metal Rich")]
     MetalRich,
     Metallic,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(AsteroidType);
+serde_with_other!(AsteroidType);
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -501,7 +577,6 @@ impl AtmosphereComposition {
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -513,6 +588,8 @@ impl AtmosphereComposition {
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
+#[serde(remote = "Self")]
 pub enum AtmosphereCompositionKey {
     Ammonia,
     Argon,
@@ -529,6 +606,10 @@ pub enum AtmosphereCompositionKey {
     #[serde(rename = "Sulphur dioxide")]
     SulphurDioxide,
     Water,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl AtmosphereCompositionKey {
@@ -536,11 +617,11 @@ impl AtmosphereCompositionKey {
 }
 
 display_via_serde!(AtmosphereCompositionKey);
+serde_with_other!(AtmosphereCompositionKey);
 
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -552,6 +633,8 @@ display_via_serde!(AtmosphereCompositionKey);
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
+#[serde(remote = "Self")]
 pub enum AtmosphereType {
     /* normal */
     Ammonia,
@@ -717,6 +800,10 @@ pub enum AtmosphereType {
     ThinWater,
     #[serde(rename = "Thin Water-rich")]
     ThinWaterRich,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl AtmosphereType {
@@ -724,6 +811,224 @@ impl AtmosphereType {
 }
 
 display_via_serde!(AtmosphereType);
+serde_with_other!(AtmosphereType);
+
+impl AtmosphereType {
+    /// Decomposes the atmosphere into density, temperature and base gas.
+    ///
+    /// Returns `None` for [`AtmosphereType::Other`].
+    pub fn descriptor(&self) -> Option<AtmosphereDescriptor> {
+        use AtmosphereBase as B;
+        use AtmosphereDensity as D;
+        let (density, hot, rich, base) = match self {
+            AtmosphereType::Ammonia => (D::Normal, false, false, B::Ammonia),
+            AtmosphereType::AmmoniaAndOxygen => (D::Normal, false, false, B::AmmoniaAndOxygen),
+            AtmosphereType::AmmoniaRich => (D::Normal, false, true, B::Ammonia),
+            AtmosphereType::Argon => (D::Normal, false, false, B::Argon),
+            AtmosphereType::ArgonRich => (D::Normal, false, true, B::Argon),
+            AtmosphereType::CarbonDioxide => (D::Normal, false, false, B::CarbonDioxide),
+            AtmosphereType::CarbonDioxideRich => (D::Normal, false, true, B::CarbonDioxide),
+            AtmosphereType::Helium => (D::Normal, false, false, B::Helium),
+            AtmosphereType::MetallicVapour => (D::Normal, false, false, B::MetallicVapour),
+            AtmosphereType::Methane => (D::Normal, false, false, B::Methane),
+            AtmosphereType::MethaneRich => (D::Normal, false, true, B::Methane),
+            AtmosphereType::Neon => (D::Normal, false, false, B::Neon),
+            AtmosphereType::NeonRich => (D::Normal, false, true, B::Neon),
+            AtmosphereType::Nitrogen => (D::Normal, false, false, B::Nitrogen),
+            AtmosphereType::NoAtmosphere => (D::Normal, false, false, B::NoAtmosphere),
+            AtmosphereType::Oxygen => (D::Normal, false, false, B::Oxygen),
+            AtmosphereType::SilicateVapour => (D::Normal, false, false, B::SilicateVapour),
+            AtmosphereType::SuitableForWaterBasedLife => {
+                (D::Normal, false, false, B::SuitableForWaterBasedLife)
+            }
+            AtmosphereType::SulphurDioxide => (D::Normal, false, false, B::SulphurDioxide),
+            AtmosphereType::Water => (D::Normal, false, false, B::Water),
+            AtmosphereType::WaterRich => (D::Normal, false, true, B::Water),
+            AtmosphereType::HotArgon => (D::Normal, true, false, B::Argon),
+            AtmosphereType::HotArgonRich => (D::Normal, true, true, B::Argon),
+            AtmosphereType::HotCarbonDioxide => (D::Normal, true, false, B::CarbonDioxide),
+            AtmosphereType::HotCarbonDioxideRich => (D::Normal, true, true, B::CarbonDioxide),
+            AtmosphereType::HotMetallicVapour => (D::Normal, true, false, B::MetallicVapour),
+            AtmosphereType::HotSilicateVapour => (D::Normal, true, false, B::SilicateVapour),
+            AtmosphereType::HotSulphurDioxide => (D::Normal, true, false, B::SulphurDioxide),
+            AtmosphereType::HotWater => (D::Normal, true, false, B::Water),
+            AtmosphereType::HotWaterRich => (D::Normal, true, true, B::Water),
+            AtmosphereType::HotThickAmmonia => (D::Thick, true, false, B::Ammonia),
+            AtmosphereType::HotThickAmmoniaRich => (D::Thick, true, true, B::Ammonia),
+            AtmosphereType::HotThickArgon => (D::Thick, true, false, B::Argon),
+            AtmosphereType::HotThickArgonRich => (D::Thick, true, true, B::Argon),
+            AtmosphereType::HotThickCarbonDioxide => (D::Thick, true, false, B::CarbonDioxide),
+            AtmosphereType::HotThickCarbonDioxideRich => (D::Thick, true, true, B::CarbonDioxide),
+            AtmosphereType::HotThickMetallicVapour => (D::Thick, true, false, B::MetallicVapour),
+            AtmosphereType::HotThickMethane => (D::Thick, true, false, B::Methane),
+            AtmosphereType::HotThickMethaneRich => (D::Thick, true, true, B::Methane),
+            AtmosphereType::HotThickNitrogen => (D::Thick, true, false, B::Nitrogen),
+            AtmosphereType::HotThickNoAtmosphere => (D::Thick, true, false, B::NoAtmosphere),
+            AtmosphereType::HotThickSilicateVapour => (D::Thick, true, false, B::SilicateVapour),
+            AtmosphereType::HotThickSulphurDioxide => (D::Thick, true, false, B::SulphurDioxide),
+            AtmosphereType::HotThickWater => (D::Thick, true, false, B::Water),
+            AtmosphereType::HotThickWaterRich => (D::Thick, true, true, B::Water),
+            AtmosphereType::HotThinCarbonDioxide => (D::Thin, true, false, B::CarbonDioxide),
+            AtmosphereType::HotThinMetallicVapour => (D::Thin, true, false, B::MetallicVapour),
+            AtmosphereType::HotThinSilicateVapour => (D::Thin, true, false, B::SilicateVapour),
+            AtmosphereType::HotThinSulphurDioxide => (D::Thin, true, false, B::SulphurDioxide),
+            AtmosphereType::ThickAmmonia => (D::Thick, false, false, B::Ammonia),
+            AtmosphereType::ThickAmmoniaAndOxygen => (D::Thick, false, false, B::AmmoniaAndOxygen),
+            AtmosphereType::ThickAmmoniaRich => (D::Thick, false, true, B::Ammonia),
+            AtmosphereType::ThickArgon => (D::Thick, false, false, B::Argon),
+            AtmosphereType::ThickArgonRich => (D::Thick, false, true, B::Argon),
+            AtmosphereType::ThickCarbonDioxide => (D::Thick, false, false, B::CarbonDioxide),
+            AtmosphereType::ThickCarbonDioxideRich => (D::Thick, false, true, B::CarbonDioxide),
+            AtmosphereType::ThickHelium => (D::Thick, false, false, B::Helium),
+            AtmosphereType::ThickMethane => (D::Thick, false, false, B::Methane),
+            AtmosphereType::ThickMethaneRich => (D::Thick, false, true, B::Methane),
+            AtmosphereType::ThickNitrogen => (D::Thick, false, false, B::Nitrogen),
+            AtmosphereType::ThickNoAtmosphere => (D::Thick, false, false, B::NoAtmosphere),
+            AtmosphereType::ThickSuitableForWaterBasedLife => {
+                (D::Thick, false, false, B::SuitableForWaterBasedLife)
+            }
+            AtmosphereType::ThickSulphurDioxide => (D::Thick, false, false, B::SulphurDioxide),
+            AtmosphereType::ThickWater => (D::Thick, false, false, B::Water),
+            AtmosphereType::ThickWaterRich => (D::Thick, false, true, B::Water),
+            AtmosphereType::ThinAmmonia => (D::Thin, false, false, B::Ammonia),
+            AtmosphereType::ThinAmmoniaAndOxygen => (D::Thin, false, false, B::AmmoniaAndOxygen),
+            AtmosphereType::ThinAmmoniaRich => (D::Thin, false, true, B::Ammonia),
+            AtmosphereType::ThinArgon => (D::Thin, false, false, B::Argon),
+            AtmosphereType::ThinArgonRich => (D::Thin, false, true, B::Argon),
+            AtmosphereType::ThinCarbonDioxide => (D::Thin, false, false, B::CarbonDioxide),
+            AtmosphereType::ThinCarbonDioxideRich => (D::Thin, false, true, B::CarbonDioxide),
+            AtmosphereType::ThinHelium => (D::Thin, false, false, B::Helium),
+            AtmosphereType::ThinMethane => (D::Thin, false, false, B::Methane),
+            AtmosphereType::ThinMethaneRich => (D::Thin, false, true, B::Methane),
+            AtmosphereType::ThinNeon => (D::Thin, false, false, B::Neon),
+            AtmosphereType::ThinNeonRich => (D::Thin, false, true, B::Neon),
+            AtmosphereType::ThinNitrogen => (D::Thin, false, false, B::Nitrogen),
+            AtmosphereType::ThinNoAtmosphere => (D::Thin, false, false, B::NoAtmosphere),
+            AtmosphereType::ThinOxygen => (D::Thin, false, false, B::Oxygen),
+            AtmosphereType::ThinSulphurDioxide => (D::Thin, false, false, B::SulphurDioxide),
+            AtmosphereType::ThinWater => (D::Thin, false, false, B::Water),
+            AtmosphereType::ThinWaterRich => (D::Thin, false, true, B::Water),
+            AtmosphereType::Other(_) => return None,
+        };
+        Some(AtmosphereDescriptor {
+            density,
+            hot,
+            rich,
+            base,
+        })
+    }
+
+    /// Inverse of [`AtmosphereType::descriptor`].
+    ///
+    /// Returns `None` for combinations that do not occur in the game.
+    pub fn from_descriptor(descriptor: AtmosphereDescriptor) -> Option<AtmosphereType> {
+        AtmosphereType::iter().find(|t| t.descriptor() == Some(descriptor))
+    }
+
+    pub fn density(&self) -> Option<AtmosphereDensity> {
+        self.descriptor().map(|d| d.density)
+    }
+
+    pub fn base(&self) -> Option<AtmosphereBase> {
+        self.descriptor().map(|d| d.base)
+    }
+
+    pub fn is_hot(&self) -> Option<bool> {
+        self.descriptor().map(|d| d.hot)
+    }
+
+    pub fn is_rich(&self) -> Option<bool> {
+        self.descriptor().map(|d| d.rich)
+    }
+}
+
+/// Structured form of an [`AtmosphereType`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AtmosphereDescriptor {
+    pub density: AtmosphereDensity,
+    pub hot: bool,
+    /// Rich in the base gas, e.g. "Carbon dioxide-rich".
+    pub rich: bool,
+    pub base: AtmosphereBase,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum AtmosphereDensity {
+    Thin,
+    Normal,
+    Thick,
+}
+
+display_via_serde!(AtmosphereDensity);
+
+/// Base gas of an [`AtmosphereType`].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    VariantCount,
+)]
+pub enum AtmosphereBase {
+    Ammonia,
+    #[serde(rename = "Ammonia and Oxygen")]
+    AmmoniaAndOxygen,
+    Argon,
+    #[serde(rename = "Carbon dioxide")]
+    CarbonDioxide,
+    Helium,
+    #[serde(rename = "Metallic vapour")]
+    MetallicVapour,
+    Methane,
+    Neon,
+    Nitrogen,
+    #[serde(rename = "No atmosphere")]
+    NoAtmosphere,
+    Oxygen,
+    #[serde(rename = "Silicate vapour")]
+    SilicateVapour,
+    #[serde(rename = "Suitable for water-based life")]
+    SuitableForWaterBasedLife,
+    #[serde(rename = "Sulphur dioxide")]
+    SulphurDioxide,
+    Water,
+}
+
+impl AtmosphereBase {
+    pub const VARIANTS: usize = 15;
+
+    /// The matching gas of [`AtmosphereComposition`], if it is a single gas.
+    pub fn composition_key(self) -> Option<AtmosphereCompositionKey> {
+        match self {
+            AtmosphereBase::Ammonia => Some(AtmosphereCompositionKey::Ammonia),
+            AtmosphereBase::Argon => Some(AtmosphereCompositionKey::Argon),
+            AtmosphereBase::CarbonDioxide => Some(AtmosphereCompositionKey::CarbonDioxide),
+            AtmosphereBase::Helium => Some(AtmosphereCompositionKey::Helium),
+            AtmosphereBase::MetallicVapour => Some(AtmosphereCompositionKey::Iron),
+            AtmosphereBase::Methane => Some(AtmosphereCompositionKey::Methane),
+            AtmosphereBase::Neon => Some(AtmosphereCompositionKey::Neon),
+            AtmosphereBase::Nitrogen => Some(AtmosphereCompositionKey::Nitrogen),
+            AtmosphereBase::Oxygen => Some(AtmosphereCompositionKey::Oxygen),
+            AtmosphereBase::SilicateVapour => Some(AtmosphereCompositionKey::Silicates),
+            AtmosphereBase::SulphurDioxide => Some(AtmosphereCompositionKey::SulphurDioxide),
+            AtmosphereBase::Water => Some(AtmosphereCompositionKey::Water),
+            AtmosphereBase::AmmoniaAndOxygen
+            | AtmosphereBase::NoAtmosphere
+            | AtmosphereBase::SuitableForWaterBasedLife => None,
+        }
+    }
+}
+
+display_via_serde!(AtmosphereBase);
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -752,7 +1057,6 @@ pub struct Discovery {
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -764,7 +1068,9 @@ pub struct Discovery {
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Luminosity {
     VII,
     VI,
@@ -791,6 +1097,10 @@ pub enum Luminosity {
     Ia,
     I,
     O,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl Luminosity {
@@ -798,6 +1108,7 @@ impl Luminosity {
 }
 
 display_via_serde!(Luminosity);
+serde_with_other!(Luminosity);
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -809,12 +1120,16 @@ impl Materials {
     pub fn get(&self, key: MaterialsKey) -> Option<f32> {
         self.0.get(&key).copied()
     }
+
+    /// Materials and their percentages, in the order of [`MaterialsKey`].
+    pub fn iter(&self) -> impl Iterator<Item = (MaterialsKey, f32)> + '_ {
+        self.0.iter().map(|(k, v)| (k.clone(), *v))
+    }
 }
 
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -826,6 +1141,8 @@ impl Materials {
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
+#[serde(remote = "Self")]
 pub enum MaterialsKey {
     Antimony,
     Arsenic,
@@ -852,27 +1169,85 @@ pub enum MaterialsKey {
     Yttrium,
     Zinc,
     Zirconium,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl MaterialsKey {
     pub const VARIANTS: usize = 25;
+
+    /// Grade of the material, `None` for [`MaterialsKey::Other`].
+    pub fn grade(&self) -> Option<MaterialGrade> {
+        let grade = match self {
+            MaterialsKey::Carbon
+            | MaterialsKey::Iron
+            | MaterialsKey::Nickel
+            | MaterialsKey::Phosphorus
+            | MaterialsKey::Sulphur => MaterialGrade::VeryCommon,
+            MaterialsKey::Arsenic
+            | MaterialsKey::Chromium
+            | MaterialsKey::Germanium
+            | MaterialsKey::Manganese
+            | MaterialsKey::Vanadium
+            | MaterialsKey::Zinc
+            | MaterialsKey::Zirconium => MaterialGrade::Common,
+            MaterialsKey::Cadmium
+            | MaterialsKey::Mercury
+            | MaterialsKey::Molybdenum
+            | MaterialsKey::Niobium
+            | MaterialsKey::Tin
+            | MaterialsKey::Tungsten => MaterialGrade::Standard,
+            MaterialsKey::Antimony
+            | MaterialsKey::Polonium
+            | MaterialsKey::Ruthenium
+            | MaterialsKey::Selenium
+            | MaterialsKey::Technetium
+            | MaterialsKey::Tellurium
+            | MaterialsKey::Yttrium => MaterialGrade::Rare,
+            MaterialsKey::Other(_) => return None,
+        };
+        Some(grade)
+    }
 }
 
 display_via_serde!(MaterialsKey);
+serde_with_other!(MaterialsKey);
+
+/// Rarity of a raw material, from grade 1 to 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaterialGrade {
+    VeryCommon = 1,
+    Common = 2,
+    Standard = 3,
+    Rare = 4,
+}
+
+impl MaterialGrade {
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Parent {
     Null(u64),
     Planet(u64),
     Star(u64),
+    /// A parent kind unknown to this model and its body ID, only produced by lenient parsing.
+    #[serde(skip)]
+    Other(String, u64),
 }
 
+serde_with_other!(Parent, tagged);
+
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -884,7 +1259,9 @@ pub enum Parent {
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum PlanetSubType {
     // gas ginat
     #[serde(rename = "Class I gas giant")]
@@ -924,12 +1301,17 @@ pub enum PlanetSubType {
     RockyBody,
     #[serde(rename = "Water world")]
     WaterWorld,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl PlanetSubType {
     pub const VARIANTS: usize = 18;
 
-    pub fn short(self) -> &'static str {
+    /// Abbreviation of the sub type, the name itself for [`PlanetSubType::Other`].
+    pub fn short(&self) -> &str {
         match self {
             PlanetSubType::ClassIGasGiant => "C1GG",
             PlanetSubType::ClassIiGasGiant => "C2GG",
@@ -949,16 +1331,17 @@ impl PlanetSubType {
             PlanetSubType::RockyIceWorld => "RI",
             PlanetSubType::RockyBody => "R",
             PlanetSubType::WaterWorld => "WW",
+            PlanetSubType::Other(s) => s,
         }
     }
 }
 
 display_via_serde!(PlanetSubType);
+serde_with_other!(PlanetSubType);
 
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -970,13 +1353,19 @@ display_via_serde!(PlanetSubType);
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum ReserveLevel {
     Depleted,
     Low,
     Common,
     Major,
     Pristine,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl ReserveLevel {
@@ -984,6 +1373,7 @@ impl ReserveLevel {
 }
 
 display_via_serde!(ReserveLevel);
+serde_with_other!(ReserveLevel);
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -1095,7 +1485,6 @@ impl fmt::Display for StarClass {
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -1107,7 +1496,9 @@ impl fmt::Display for StarClass {
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum SpectralClass {
     O,
     O0,
@@ -1237,18 +1628,183 @@ pub enum SpectralClass {
     TTS7,
     TTS8,
     TTS9,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl SpectralClass {
     pub const VARIANTS: usize = 128;
+
+    /// Main class, [`StarClass::ProtoStars`] for the Herbig Ae/Be and T Tauri classes.
+    ///
+    /// Returns `None` for [`SpectralClass::Other`].
+    pub fn letter(&self) -> Option<StarClass> {
+        self.parts().map(|(letter, _)| letter)
+    }
+
+    /// Subclass from 0 (hottest) to 9, if given.
+    pub fn subclass(&self) -> Option<u8> {
+        self.parts().and_then(|(_, subclass)| subclass)
+    }
+
+    /// Inverse of [`SpectralClass::letter`] and [`SpectralClass::subclass`].
+    ///
+    /// Returns `None` for the proto star classes, which cannot be told apart by letter.
+    pub fn from_parts(letter: StarClass, subclass: Option<u8>) -> Option<SpectralClass> {
+        if letter == StarClass::ProtoStars {
+            return None;
+        }
+        SpectralClass::iter().find(|c| c.parts() == Some((letter, subclass)))
+    }
+
+    fn parts(&self) -> Option<(StarClass, Option<u8>)> {
+        static PARTS: OnceLock<HashMap<SpectralClass, (StarClass, Option<u8>)>> = OnceLock::new();
+        let parts = PARTS.get_or_init(|| {
+            SpectralClass::iter()
+                .map(|c| {
+                    let name = c.to_string();
+                    let (letter, digits) = if name.starts_with("AeBe") || name.starts_with("TTS") {
+                        (
+                            StarClass::ProtoStars,
+                            name.trim_start_matches(char::is_alphabetic),
+                        )
+                    } else {
+                        let letter = StarClass::iter()
+                            .find(|l| name.starts_with(l.short()))
+                            .expect("spectral class starts with a class letter");
+                        (letter, &name[1..])
+                    };
+                    (c, (letter, digits.parse().ok()))
+                })
+                .collect()
+        });
+        parts.get(self).copied()
+    }
+
+    /// Position in the temperature sequence, higher is hotter.
+    ///
+    /// Classes without a subclass sort between subclasses 4 and 5.
+    /// Herbig Ae/Be classes rank with A classes and T Tauri classes with K classes, the main
+    /// sequence classes of similar temperature. [`SpectralClass::Other`] comes before all others.
+    fn temperature_rank(&self) -> u16 {
+        let Some((letter, subclass)) = self.parts() else {
+            return 0;
+        };
+        let letter = match letter {
+            StarClass::OTypeStars => 9,
+            StarClass::BTypeStars => 8,
+            StarClass::ATypeStars => 7,
+            StarClass::FTypeStars => 6,
+            StarClass::GTypeStars => 5,
+            StarClass::KTypeStars => 4,
+            StarClass::MTypeStars => 3,
+            StarClass::LTypeStars => 2,
+            StarClass::TTypeStars => 1,
+            StarClass::YTypeStars => 0,
+            StarClass::ProtoStars if self.is_herbig_ae_be() => 7,
+            StarClass::ProtoStars => 4,
+            _ => return 0,
+        };
+        let subclass = match subclass {
+            Some(n) => 2 * (9 - n as u16),
+            None => 9,
+        };
+        1 + 20 * letter + subclass
+    }
+
+    fn is_herbig_ae_be(&self) -> bool {
+        matches!(
+            self,
+            SpectralClass::AeBe0
+                | SpectralClass::AeBe1
+                | SpectralClass::AeBe2
+                | SpectralClass::AeBe3
+                | SpectralClass::AeBe4
+                | SpectralClass::AeBe5
+                | SpectralClass::AeBe6
+                | SpectralClass::AeBe7
+                | SpectralClass::AeBe8
+                | SpectralClass::AeBe9
+        )
+    }
 }
 
 display_via_serde!(SpectralClass);
+serde_with_other!(SpectralClass);
+
+/// Spectral class with optional luminosity class, e.g. "K5 Vab".
+///
+/// Ordered by temperature, coolest first, then by luminosity class.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StellarClassification {
+    pub class: SpectralClass,
+    pub luminosity: Option<Luminosity>,
+}
+
+impl StellarClassification {
+    /// Returns `true` if the spectral class agrees with the class of `sub_type`.
+    ///
+    /// Unknown classes are consistent with anything.
+    pub fn is_consistent_with(&self, sub_type: &StarSubType) -> bool {
+        match (self.class.letter(), sub_type.filter_star_class()) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        }
+    }
+}
+
+impl PartialOrd for StellarClassification {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StellarClassification {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.class
+            .temperature_rank()
+            .cmp(&other.class.temperature_rank())
+            .then_with(|| self.luminosity.cmp(&other.luminosity))
+            .then_with(|| self.class.cmp(&other.class))
+    }
+}
+
+impl fmt::Display for StellarClassification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.luminosity {
+            Some(luminosity) => write!(f, "{} {}", self.class, luminosity),
+            None => write!(f, "{}", self.class),
+        }
+    }
+}
+
+impl std::str::FromStr for StellarClassification {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<StellarClassification> {
+        let mut words = s.split_whitespace();
+        let (Some(class), luminosity, None) = (words.next(), words.next(), words.next()) else {
+            bail!("invalid stellar classification: {:?}", s);
+        };
+        let Some(class) = SpectralClass::iter().find(|c| c.to_string() == class) else {
+            bail!("unknown spectral class: {:?}", class);
+        };
+        let luminosity = match luminosity {
+            Some(l) => match Luminosity::iter().find(|v| v.to_string() == l) {
+                Some(l) => Some(l),
+                None => bail!("unknown luminosity class: {:?}", l),
+            },
+            None => None,
+        };
+        Ok(StellarClassification { class, luminosity })
+    }
+}
 
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -1260,7 +1816,9 @@ display_via_serde!(SpectralClass);
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum StarSubType {
     // Main sequence
     #[serde(rename = "O (Blue-White) Star")]
@@ -1357,12 +1915,17 @@ pub enum StarSubType {
     BlackHole,
     #[serde(rename = "Supermassive Black Hole")]
     SupermassiveBlackHole,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl StarSubType {
     pub const VARIANTS: usize = 43;
 
-    pub fn short(self) -> &'static str {
+    /// Abbreviation of the sub type, the name itself for [`StarSubType::Other`].
+    pub fn short(&self) -> &str {
         match self {
             StarSubType::OBlueWhiteStar => "O",
             StarSubType::BBlueWhiteStar => "B",
@@ -1407,15 +1970,18 @@ impl StarSubType {
             StarSubType::NeutronStar => "N",
             StarSubType::BlackHole => "BH",
             StarSubType::SupermassiveBlackHole => "sBH",
+            StarSubType::Other(s) => s,
         }
     }
 }
 
 display_via_serde!(StarSubType);
+serde_with_other!(StarSubType);
 
 impl StarSubType {
-    pub fn filter_star_class(self) -> StarClass {
-        match self {
+    /// Class of the sub type, `None` for [`StarSubType::Other`].
+    pub fn filter_star_class(&self) -> Option<StarClass> {
+        let class = match self {
             StarSubType::OBlueWhiteStar => StarClass::OTypeStars,
             StarSubType::BBlueWhiteSuperGiantStar => StarClass::BTypeStars,
             StarSubType::BBlueWhiteStar => StarClass::BTypeStars,
@@ -1459,14 +2025,15 @@ impl StarSubType {
             StarSubType::NeutronStar => StarClass::NonSequenceStars,
             StarSubType::BlackHole => StarClass::NonSequenceStars,
             StarSubType::SupermassiveBlackHole => StarClass::NonSequenceStars,
-        }
+            StarSubType::Other(_) => return None,
+        };
+        Some(class)
     }
 }
 
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -1478,7 +2045,9 @@ impl StarSubType {
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum TerraformingState {
     #[serde(rename = "Candidate for terraforming")]
     CandidateForTerraforming,
@@ -1486,6 +2055,10 @@ pub enum TerraformingState {
     NotTerraformable,
     Terraformed,
     Terraforming,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl TerraformingState {
@@ -1493,11 +2066,11 @@ impl TerraformingState {
 }
 
 display_via_serde!(TerraformingState);
+serde_with_other!(TerraformingState);
 
 #[derive(
     Debug,
     Clone,
-    Copy,
     PartialEq,
     Eq,
     PartialOrd,
@@ -1509,7 +2082,9 @@ display_via_serde!(TerraformingState);
     VariantCount,
 )]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[cfg_attr(test, derive(strum::EnumDiscriminants))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum VolcanismType {
     #[serde(rename = "Ammonia Magma")]
     AmmoniaMagma,
@@ -1561,13 +2136,253 @@ pub enum VolcanismType {
     WaterGeysers,
     #[serde(rename = "Water Magma")]
     WaterMagma,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 impl VolcanismType {
     pub const VARIANTS: usize = 25;
+
+    /// Decomposes the volcanism into intensity, kind and substance.
+    ///
+    /// Returns `None` for [`VolcanismType::NoVolcanism`] and [`VolcanismType::Other`].
+    pub fn volcanism(&self) -> Option<Volcanism> {
+        let (intensity, kind, substance) = match self {
+            VolcanismType::AmmoniaMagma => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Ammonia,
+            ),
+            VolcanismType::CarbonDioxideGeysers => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::CarbonDioxide,
+            ),
+            VolcanismType::MajorCarbonDioxideGeysers => (
+                VolcanismIntensity::Major,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::CarbonDioxide,
+            ),
+            VolcanismType::MajorMetallicMagma => (
+                VolcanismIntensity::Major,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Metallic,
+            ),
+            VolcanismType::MajorRockyMagma => (
+                VolcanismIntensity::Major,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Rocky,
+            ),
+            VolcanismType::MajorSilicateVapourGeysers => (
+                VolcanismIntensity::Major,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::SilicateVapour,
+            ),
+            VolcanismType::MajorWaterGeysers => (
+                VolcanismIntensity::Major,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::Water,
+            ),
+            VolcanismType::MajorWaterMagma => (
+                VolcanismIntensity::Major,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Water,
+            ),
+            VolcanismType::MetallicMagma => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Metallic,
+            ),
+            VolcanismType::MethaneMagma => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Methane,
+            ),
+            VolcanismType::MinorAmmoniaMagma => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Ammonia,
+            ),
+            VolcanismType::MinorCarbonDioxideGeysers => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::CarbonDioxide,
+            ),
+            VolcanismType::MinorMetallicMagma => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Metallic,
+            ),
+            VolcanismType::MinorMethaneMagma => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Methane,
+            ),
+            VolcanismType::MinorNitrogenMagma => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Nitrogen,
+            ),
+            VolcanismType::MinorRockyMagma => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Rocky,
+            ),
+            VolcanismType::MinorSilicateVapourGeysers => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::SilicateVapour,
+            ),
+            VolcanismType::MinorWaterGeysers => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::Water,
+            ),
+            VolcanismType::MinorWaterMagma => (
+                VolcanismIntensity::Minor,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Water,
+            ),
+            VolcanismType::NitrogenMagma => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Nitrogen,
+            ),
+            VolcanismType::RockyMagma => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Rocky,
+            ),
+            VolcanismType::SilicateVapourGeysers => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::SilicateVapour,
+            ),
+            VolcanismType::WaterGeysers => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Geysers,
+                VolcanismSubstance::Water,
+            ),
+            VolcanismType::WaterMagma => (
+                VolcanismIntensity::Normal,
+                VolcanismKind::Magma,
+                VolcanismSubstance::Water,
+            ),
+            VolcanismType::NoVolcanism | VolcanismType::Other(_) => return None,
+        };
+        Some(Volcanism {
+            intensity,
+            kind,
+            substance,
+        })
+    }
+
+    /// Inverse of [`VolcanismType::volcanism`].
+    ///
+    /// Returns `None` for combinations that do not occur in the game.
+    pub fn from_volcanism(volcanism: Volcanism) -> Option<VolcanismType> {
+        VolcanismType::iter().find(|t| t.volcanism() == Some(volcanism))
+    }
+
+    pub fn intensity(&self) -> Option<VolcanismIntensity> {
+        self.volcanism().map(|v| v.intensity)
+    }
+
+    pub fn kind(&self) -> Option<VolcanismKind> {
+        self.volcanism().map(|v| v.kind)
+    }
+
+    pub fn substance(&self) -> Option<VolcanismSubstance> {
+        self.volcanism().map(|v| v.substance)
+    }
+
+    /// Returns `true` unless this is [`VolcanismType::NoVolcanism`].
+    pub fn is_active(&self) -> bool {
+        *self != VolcanismType::NoVolcanism
+    }
+
+    /// All types with volcanism.
+    pub fn active() -> impl Iterator<Item = VolcanismType> {
+        VolcanismType::iter().filter(|t| t.is_active())
+    }
 }
 
 display_via_serde!(VolcanismType);
+serde_with_other!(VolcanismType);
+
+/// Structured form of a [`VolcanismType`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Volcanism {
+    pub intensity: VolcanismIntensity,
+    pub kind: VolcanismKind,
+    pub substance: VolcanismSubstance,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum VolcanismIntensity {
+    Minor,
+    Normal,
+    Major,
+}
+
+display_via_serde!(VolcanismIntensity);
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum VolcanismKind {
+    Magma,
+    Geysers,
+}
+
+display_via_serde!(VolcanismKind);
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum VolcanismSubstance {
+    Ammonia,
+    #[serde(rename = "Carbon Dioxide")]
+    CarbonDioxide,
+    Metallic,
+    Methane,
+    Nitrogen,
+    Rocky,
+    #[serde(rename = "Silicate Vapour")]
+    SilicateVapour,
+    Water,
+}
+
+display_via_serde!(VolcanismSubstance);
+
+/// Selects volcanism types by their parts. `None` fields accept anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VolcanismFilter {
+    pub intensity: Option<VolcanismIntensity>,
+    pub kind: Option<VolcanismKind>,
+    pub substance: Option<VolcanismSubstance>,
+}
+
+impl VolcanismFilter {
+    /// Returns `true` if `volcanism` is active and has the selected parts.
+    pub fn matches(&self, volcanism: Option<&VolcanismType>) -> bool {
+        let Some(v) = volcanism.and_then(VolcanismType::volcanism) else {
+            return false;
+        };
+        self.intensity.map_or(true, |i| i == v.intensity)
+            && self.kind.map_or(true, |k| k == v.kind)
+            && self.substance.map_or(true, |s| s == v.substance)
+    }
+
+    /// Volcanism types selected by this filter.
+    pub fn types(self) -> impl Iterator<Item = VolcanismType> {
+        VolcanismType::active().filter(move |t| self.matches(Some(t)))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1579,6 +2394,25 @@ mod tests {
         let mut max = 0;
         let n = AtmosphereCompositionKey::VARIANTS;
         for v in AtmosphereCompositionKey::iter() {
+            let x = AtmosphereCompositionKeyDiscriminants::from(&v) as usize;
+            assert!(
+                x < n,
+                "type index ({}) should be smaller than variants count ({})",
+                x,
+                n
+            );
+            max = max.max(x);
+        }
+        assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(AtmosphereCompositionKeyDiscriminants::Other as usize, n);
+    }
+
+    #[test]
+    fn atmosphere_base_check_variants_count() {
+        let mut max = 0;
+        let n = AtmosphereBase::VARIANTS;
+        for v in AtmosphereBase::iter() {
             let x = v as usize;
             assert!(
                 x < n,
@@ -1596,7 +2430,7 @@ mod tests {
         let mut max = 0;
         let n = AtmosphereType::VARIANTS;
         for v in AtmosphereType::iter() {
-            let x = v as usize;
+            let x = AtmosphereTypeDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1606,6 +2440,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(AtmosphereTypeDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1613,7 +2449,7 @@ mod tests {
         let mut max = 0;
         let n = Luminosity::VARIANTS;
         for v in Luminosity::iter() {
-            let x = v as usize;
+            let x = LuminosityDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1623,6 +2459,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(LuminosityDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1630,7 +2468,7 @@ mod tests {
         let mut max = 0;
         let n = MaterialsKey::VARIANTS;
         for v in MaterialsKey::iter() {
-            let x = v as usize;
+            let x = MaterialsKeyDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1640,6 +2478,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(MaterialsKeyDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1647,7 +2487,7 @@ mod tests {
         let mut max = 0;
         let n = PlanetSubType::VARIANTS;
         for v in PlanetSubType::iter() {
-            let x = v as usize;
+            let x = PlanetSubTypeDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1657,6 +2497,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(PlanetSubTypeDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1664,7 +2506,7 @@ mod tests {
         let mut max = 0;
         let n = ReserveLevel::VARIANTS;
         for v in ReserveLevel::iter() {
-            let x = v as usize;
+            let x = ReserveLevelDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1674,6 +2516,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(ReserveLevelDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1698,7 +2542,7 @@ mod tests {
         let mut max = 0;
         let n = SpectralClass::VARIANTS;
         for v in SpectralClass::iter() {
-            let x = v as usize;
+            let x = SpectralClassDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1708,6 +2552,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(SpectralClassDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1715,7 +2561,7 @@ mod tests {
         let mut max = 0;
         let n = StarSubType::VARIANTS;
         for v in StarSubType::iter() {
-            let x = v as usize;
+            let x = StarSubTypeDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1725,6 +2571,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(StarSubTypeDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1732,7 +2580,7 @@ mod tests {
         let mut max = 0;
         let n = TerraformingState::VARIANTS;
         for v in TerraformingState::iter() {
-            let x = v as usize;
+            let x = TerraformingStateDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1742,6 +2590,8 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(TerraformingStateDiscriminants::Other as usize, n);
     }
 
     #[test]
@@ -1749,7 +2599,7 @@ mod tests {
         let mut max = 0;
         let n = VolcanismType::VARIANTS;
         for v in VolcanismType::iter() {
-            let x = v as usize;
+            let x = VolcanismTypeDiscriminants::from(&v) as usize;
             assert!(
                 x < n,
                 "type index ({}) should be smaller than variants count ({})",
@@ -1759,5 +2609,7 @@ mod tests {
             max = max.max(x);
         }
         assert_eq!(n, max + 1);
+        // `Other` is not iterated and comes last.
+        assert_eq!(VolcanismTypeDiscriminants::Other as usize, n);
     }
 }
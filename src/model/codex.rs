@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::dec::date_format;
+use super::RootEntry;
+
+// Main Type
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Codex {
+    pub system_id: u64,
+    // Attributes
+    pub name: String,
+    pub region: String,
+    pub system_id64: Option<u64>,
+    pub system_name: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+    // Metadata
+    #[serde(with = "date_format")]
+    #[cfg_attr(feature = "type_hash", type_hash(foreign_type))]
+    pub reported_on: DateTime<Utc>,
+}
+
+impl RootEntry for Codex {
+    /// Codex entries have no ID of their own, so this is the ID of the system.
+    fn entry_id(&self) -> u64 {
+        self.system_id
+    }
+
+    fn type_name() -> &'static str {
+        "codex"
+    }
+
+    fn time(&self) -> DateTime<Utc> {
+        self.reported_on
+    }
+}
@@ -0,0 +1,731 @@
+//! Lenient parsing that tolerates schema drift.
+//!
+//! Strict parsing rejects any field or enum value missing from the models.
+//! Lenient parsing first walks the raw JSON against the shape of the model and removes unknown
+//! fields. The entry is then parsed in lenient mode, where unknown enum values are loaded into the
+//! `Other` variant of their enum. Both are reported next to the parsed value.
+//!
+//! Unknown enum values used as map keys, such as a new material, are removed instead.
+
+use std::any::{type_name, TypeId};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::de::value::{Error as ProbeError, StrDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_json::{from_slice, json, Map, Value};
+
+use super::body::{Body, Planet, Star};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lenient<T> {
+    pub value: T,
+    /// Removed fields, keyed by their JSON path.
+    pub unknown_fields: BTreeMap<String, Value>,
+    pub unknown_variants: Vec<UnknownVariant>,
+}
+
+impl<T> Lenient<T> {
+    /// Returns `true` if nothing was unknown, i.e. strict parsing would succeed.
+    pub fn is_exact(&self) -> bool {
+        self.unknown_fields.is_empty() && self.unknown_variants.is_empty()
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Lenient<U> {
+        Lenient {
+            value: f(self.value),
+            unknown_fields: self.unknown_fields,
+            unknown_variants: self.unknown_variants,
+        }
+    }
+}
+
+impl<T> Lenient<Result<T>> {
+    /// Returns the error of a failed parse, dropping what was found.
+    pub fn transpose(self) -> Result<Lenient<T>> {
+        let value = self.value?;
+        Ok(Lenient {
+            value,
+            unknown_fields: self.unknown_fields,
+            unknown_variants: self.unknown_variants,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnknownVariant {
+    /// JSON path of the value.
+    pub path: String,
+    /// Name of the enum type.
+    pub enum_type: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown {} `{}` at {}",
+            self.enum_type, self.value, self.path
+        )
+    }
+}
+
+thread_local! {
+    static ACCEPT_OTHER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns `true` while enums with an `Other` variant load unknown values into it.
+pub(crate) fn accepts_other() -> bool {
+    ACCEPT_OTHER.with(Cell::get)
+}
+
+/// Runs `f` in lenient mode, see [`accepts_other`].
+pub(crate) fn accepting_other<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ACCEPT_OTHER.with(|a| a.set(self.0));
+        }
+    }
+
+    let _restore = Restore(ACCEPT_OTHER.with(|a| a.replace(true)));
+    f()
+}
+
+/// Parses `bs` as `T`, removing unknown fields and loading unknown enum values as `Other`.
+pub fn parse_lenient<T: DeserializeOwned + 'static>(bs: &[u8]) -> Result<Lenient<T>> {
+    parse_lenient_or(bs, |_| None).transpose()
+}
+
+/// Like [`parse_lenient`], but tries `fallback` on the original bytes if the model rejects the
+/// entry. What the walk found is kept even if the entry cannot be parsed.
+pub(crate) fn parse_lenient_or<T, F>(bs: &[u8], fallback: F) -> Lenient<Result<T>>
+where
+    T: DeserializeOwned + 'static,
+    F: FnOnce(&[u8]) -> Option<T>,
+{
+    let mut findings = Findings::default();
+    let value = from_slice(bs)
+        .context("parsing entry")
+        .and_then(|mut value| {
+            findings = scan::<T>(&mut value);
+            match accepting_other(|| serde_path_to_error::deserialize(value)) {
+                Ok(value) => Ok(value),
+                Err(e) => fallback(bs).ok_or(e).context("parsing entry"),
+            }
+        });
+    Lenient {
+        value,
+        unknown_fields: findings.unknown_fields,
+        unknown_variants: findings.unknown_variants,
+    }
+}
+
+/// Unknown fields and enum values found by [`scan`].
+#[derive(Debug, Default)]
+struct Findings {
+    unknown_fields: BTreeMap<String, Value>,
+    unknown_variants: Vec<UnknownVariant>,
+}
+
+/// Walks `value` against the shape of `T`, removing unknown fields and reporting unknown enum
+/// values.
+fn scan<T: DeserializeOwned + 'static>(value: &mut Value) -> Findings {
+    let mut findings = Findings::default();
+    walk(value, schema::<T>(), "", &mut findings);
+    findings
+}
+
+// Schemas
+
+/// Shape of a model type, as far as the walk needs it.
+#[derive(Debug, Clone)]
+enum Schema {
+    /// Not checked, e.g. strings, numbers and untagged enums.
+    Any,
+    Seq(Box<Schema>),
+    Tuple(Vec<Schema>),
+    Map(Box<Schema>, Box<Schema>),
+    Struct(Vec<(&'static str, Schema)>),
+    /// Externally tagged enum, with the payload of each variant.
+    Enum(&'static str, Vec<(&'static str, Option<Schema>)>),
+    /// Internally tagged enum: name, tag and the fields of each variant.
+    Tagged(&'static str, &'static str, Vec<(&'static str, Schema)>),
+    Ref(&'static Schema),
+}
+
+impl Schema {
+    fn resolve(&self) -> &Schema {
+        match self {
+            Schema::Ref(s) => s.resolve(),
+            s => s,
+        }
+    }
+
+    /// Combines the schemas of two probe rounds, which differ in the enum variants taken.
+    fn merge(self, other: Schema) -> Schema {
+        fn zip<K: PartialEq, V>(
+            a: Vec<(K, V)>,
+            b: Vec<(K, V)>,
+            f: impl Fn(V, V) -> V,
+        ) -> Vec<(K, V)> {
+            a.into_iter()
+                .zip(b)
+                .map(|((k, x), (_, y))| (k, f(x, y)))
+                .collect()
+        }
+
+        match (self, other) {
+            (Schema::Any, s) | (s, Schema::Any) => s,
+            (Schema::Seq(a), Schema::Seq(b)) => Schema::Seq(Box::new(a.merge(*b))),
+            (Schema::Tuple(a), Schema::Tuple(b)) => {
+                Schema::Tuple(a.into_iter().zip(b).map(|(a, b)| a.merge(b)).collect())
+            }
+            (Schema::Map(ak, av), Schema::Map(bk, bv)) => {
+                Schema::Map(Box::new(ak.merge(*bk)), Box::new(av.merge(*bv)))
+            }
+            (Schema::Struct(a), Schema::Struct(b)) => Schema::Struct(zip(a, b, Schema::merge)),
+            (Schema::Enum(name, a), Schema::Enum(_, b)) => Schema::Enum(
+                name,
+                zip(a, b, |a, b| match (a, b) {
+                    (Some(a), Some(b)) => Some(a.merge(b)),
+                    (a, b) => a.or(b),
+                }),
+            ),
+            (a, _) => a,
+        }
+    }
+}
+
+/// Schema of `T`, built once per type.
+fn schema<T: DeserializeOwned + 'static>() -> &'static Schema {
+    static SCHEMAS: OnceLock<Mutex<HashMap<TypeId, &'static Schema>>> = OnceLock::new();
+    let schemas = SCHEMAS.get_or_init(Default::default);
+    let id = TypeId::of::<T>();
+    if let Some(s) = schemas.lock().unwrap().get(&id) {
+        return s;
+    }
+
+    // Built without the lock held, since nested types look up their own schemas.
+    let built = if id == TypeId::of::<Body>() {
+        Schema::Tagged(
+            "Body",
+            "type",
+            vec![
+                ("Planet", Schema::Ref(schema::<Planet>())),
+                ("Star", Schema::Ref(schema::<Star>())),
+                // `Body::Unknown` accepts any fields.
+                ("null", Schema::Any),
+            ],
+        )
+    } else {
+        probe::<T>()
+    };
+    let mut schemas = schemas.lock().unwrap();
+    let schema = *schemas
+        .entry(id)
+        .or_insert_with(|| Box::leak(Box::new(built)));
+    schema
+}
+
+/// Builds the schema of `T` by deserializing it from a [`Probe`].
+///
+/// Each round takes the n-th variant of every enum, until the payloads of all variants are known.
+fn probe<T: DeserializeOwned>() -> Schema {
+    let mut schema = Schema::Any;
+    let mut index = 0;
+    loop {
+        let round = Round {
+            index,
+            variants: Cell::new(0),
+        };
+        let mut out = Schema::Any;
+        // Whatever was recorded before a failure is still valid.
+        let _ = T::deserialize(Probe {
+            round: &round,
+            out: &mut out,
+        });
+        schema = schema.merge(out);
+        index += 1;
+        if index >= round.variants.get() {
+            return schema;
+        }
+    }
+}
+
+/// A string that every string field accepts, including dates.
+const PROBE_STR: &str = "2000-01-01 00:00:00";
+
+struct Round {
+    index: usize,
+    /// Largest number of variants of the enums seen.
+    variants: Cell<usize>,
+}
+
+/// Deserializer that answers every request with a minimal value and records what was requested.
+struct Probe<'a> {
+    round: &'a Round,
+    out: &'a mut Schema,
+}
+
+impl<'a> Probe<'a> {
+    fn new(round: &'a Round, out: &'a mut Schema) -> Probe<'a> {
+        Probe { round, out }
+    }
+}
+
+/// Deserializes `seed` from a probe writing to `out`.
+///
+/// [`Body`] is internally tagged and cannot be probed; its schema is built separately.
+fn probe_seed<'de, S: DeserializeSeed<'de>>(
+    seed: S,
+    round: &Round,
+    out: &mut Schema,
+) -> Result<S::Value, ProbeError> {
+    if type_name::<S>() == type_name::<PhantomData<Body>>() {
+        *out = Schema::Ref(schema::<Body>());
+        let unknown = json!({"type": "null", "id": 0, "name": "", "updateTime": PROBE_STR});
+        return seed.deserialize(unknown).map_err(de::Error::custom);
+    }
+    seed.deserialize(Probe::new(round, out))
+}
+
+impl<'de> de::Deserializer<'de> for Probe<'_> {
+    type Error = ProbeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_str(PROBE_STR)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_i8(0)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_i16(0)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_i32(0)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_u8(0)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_u16(0)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_u32(0)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_f32(0.0)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_char('0')
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_bytes(PROBE_STR.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        *self.out = Schema::Seq(Box::new(Schema::Any));
+        let Schema::Seq(element) = self.out else {
+            unreachable!()
+        };
+        visitor.visit_seq(SeqProbe {
+            round: self.round,
+            slots: std::iter::once(&mut **element),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        *self.out = Schema::Tuple(vec![Schema::Any; len]);
+        let Schema::Tuple(elements) = self.out else {
+            unreachable!()
+        };
+        visitor.visit_seq(SeqProbe {
+            round: self.round,
+            slots: elements.iter_mut(),
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ProbeError> {
+        *self.out = Schema::Map(Box::new(Schema::Any), Box::new(Schema::Any));
+        let Schema::Map(key, value) = self.out else {
+            unreachable!()
+        };
+        visitor.visit_map(MapProbe {
+            round: self.round,
+            key: Some(key),
+            value: Some(value),
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        // All fields are recorded up front, in case probing stops early.
+        *self.out = Schema::Struct(fields.iter().map(|f| (*f, Schema::Any)).collect());
+        let Schema::Struct(fields) = self.out else {
+            unreachable!()
+        };
+        visitor.visit_map(StructProbe {
+            round: self.round,
+            fields: fields.iter_mut(),
+            value: None,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        let round = self.round;
+        round.variants.set(round.variants.get().max(variants.len()));
+        let index = round.index.min(variants.len() - 1);
+        let mut payloads: Vec<_> = variants.iter().map(|v| (*v, None)).collect();
+        let mut payload = Schema::Any;
+        let result = visitor.visit_enum(EnumProbe {
+            round,
+            variant: variants[index],
+            payload: &mut payload,
+        });
+        payloads[index].1 = Some(payload);
+        *self.out = Schema::Enum(name, payloads);
+        result
+    }
+
+    forward_to_deserialize_any! {
+        str string identifier
+    }
+}
+
+struct SeqProbe<'a, I> {
+    round: &'a Round,
+    slots: I,
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a mut Schema>> SeqAccess<'de> for SeqProbe<'a, I> {
+    type Error = ProbeError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, ProbeError> {
+        match self.slots.next() {
+            Some(out) => probe_seed(seed, self.round, out).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapProbe<'a> {
+    round: &'a Round,
+    key: Option<&'a mut Schema>,
+    value: Option<&'a mut Schema>,
+}
+
+impl<'de> MapAccess<'de> for MapProbe<'_> {
+    type Error = ProbeError;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, ProbeError> {
+        match self.key.take() {
+            Some(out) => probe_seed(seed, self.round, out).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, ProbeError> {
+        let out = self.value.take().expect("value after key");
+        probe_seed(seed, self.round, out)
+    }
+}
+
+struct StructProbe<'a, I> {
+    round: &'a Round,
+    fields: I,
+    value: Option<&'a mut Schema>,
+}
+
+impl<'de, 'a, I> MapAccess<'de> for StructProbe<'a, I>
+where
+    I: Iterator<Item = &'a mut (&'static str, Schema)>,
+{
+    type Error = ProbeError;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, ProbeError> {
+        let Some((name, out)) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.value = Some(out);
+        let name: StrDeserializer<ProbeError> = (*name).into_deserializer();
+        seed.deserialize(name).map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, ProbeError> {
+        let out = self.value.take().expect("value after key");
+        probe_seed(seed, self.round, out)
+    }
+}
+
+struct EnumProbe<'a> {
+    round: &'a Round,
+    variant: &'static str,
+    payload: &'a mut Schema,
+}
+
+impl<'de, 'a> EnumAccess<'de> for EnumProbe<'a> {
+    type Error = ProbeError;
+    type Variant = Probe<'a>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Probe<'a>), ProbeError> {
+        let variant: StrDeserializer<ProbeError> = self.variant.into_deserializer();
+        let value = seed.deserialize(variant)?;
+        Ok((value, Probe::new(self.round, self.payload)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Probe<'_> {
+    type Error = ProbeError;
+
+    fn unit_variant(self) -> Result<(), ProbeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, ProbeError> {
+        probe_seed(seed, self.round, self.out)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ProbeError> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+// Walk
+
+fn key_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn walk(value: &mut Value, schema: &Schema, path: &str, findings: &mut Findings) {
+    match (schema.resolve(), value) {
+        (Schema::Seq(element), Value::Array(a)) => {
+            for (i, v) in a.iter_mut().enumerate() {
+                walk(v, element, &format!("{}[{}]", path, i), findings);
+            }
+        }
+        (Schema::Tuple(elements), Value::Array(a)) => {
+            for (i, (v, s)) in a.iter_mut().zip(elements).enumerate() {
+                walk(v, s, &format!("{}[{}]", path, i), findings);
+            }
+        }
+        (Schema::Map(key, values), Value::Object(m)) => {
+            if let Schema::Enum(name, variants) = key.resolve() {
+                m.retain(|k, _| {
+                    let known = variants.iter().any(|(v, _)| v == k);
+                    if !known {
+                        findings.unknown_variants.push(UnknownVariant {
+                            path: key_path(path, k),
+                            enum_type: name,
+                            value: k.clone(),
+                        });
+                    }
+                    known
+                });
+            }
+            for (k, v) in m.iter_mut() {
+                walk(v, values, &key_path(path, k), findings);
+            }
+        }
+        (Schema::Struct(fields), Value::Object(m)) => walk_fields(m, fields, None, path, findings),
+        (Schema::Enum(name, variants), value) => walk_enum(value, name, variants, path, findings),
+        (Schema::Tagged(name, tag, variants), Value::Object(m)) => {
+            let Some(Value::String(kind)) = m.get(*tag) else {
+                return;
+            };
+            match variants.iter().find(|(v, _)| v == kind) {
+                Some((_, s)) => {
+                    if let Schema::Struct(fields) = s.resolve() {
+                        walk_fields(m, fields, Some(tag), path, findings);
+                    }
+                }
+                None => findings.unknown_variants.push(UnknownVariant {
+                    path: key_path(path, tag),
+                    enum_type: name,
+                    value: kind.clone(),
+                }),
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_fields(
+    m: &mut Map<String, Value>,
+    fields: &[(&'static str, Schema)],
+    tag: Option<&str>,
+    path: &str,
+    findings: &mut Findings,
+) {
+    let unknown: Vec<String> = m
+        .keys()
+        .filter(|k| Some(k.as_str()) != tag && !fields.iter().any(|(f, _)| f == k))
+        .cloned()
+        .collect();
+    for k in unknown {
+        let v = m.remove(&k).unwrap_or_default();
+        findings.unknown_fields.insert(key_path(path, &k), v);
+    }
+    for (f, s) in fields {
+        if let Some(v) = m.get_mut(*f) {
+            walk(v, s, &key_path(path, f), findings);
+        }
+    }
+}
+
+fn walk_enum(
+    value: &mut Value,
+    name: &'static str,
+    variants: &[(&'static str, Option<Schema>)],
+    path: &str,
+    findings: &mut Findings,
+) {
+    match value {
+        Value::String(s) if !variants.iter().any(|(v, _)| v == s) => {
+            findings.unknown_variants.push(UnknownVariant {
+                path: path.to_owned(),
+                enum_type: name,
+                value: s.clone(),
+            });
+        }
+        Value::Object(m) if m.len() == 1 => {
+            let (k, v) = m.iter_mut().next().unwrap();
+            match variants.iter().find(|(variant, _)| variant == k) {
+                Some((_, Some(payload))) => walk(v, payload, &key_path(path, k), findings),
+                Some((_, None)) => {}
+                None => findings.unknown_variants.push(UnknownVariant {
+                    path: path.to_owned(),
+                    enum_type: name,
+                    value: k.clone(),
+                }),
+            }
+        }
+        _ => {}
+    }
+}
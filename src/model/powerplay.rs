@@ -5,11 +5,11 @@ use variant_count::VariantCount;
 
 use super::bgs;
 use super::dec::date_format;
-use super::system;
+use super::system::{self, HasCoords};
 use super::RootEntry;
 
 use super::util::DisplayViaSerde;
-use crate::display_via_serde;
+use crate::{display_via_serde, serde_with_other};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -46,11 +46,18 @@ impl RootEntry for PowerPlay {
     }
 }
 
+impl HasCoords for PowerPlay {
+    fn coords(&self) -> system::Coords {
+        self.coords
+    }
+}
+
 // Field Type
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum Power {
     #[serde(rename = "A. Lavigny-Duval")]
     ALavignyDuval,
@@ -78,13 +85,19 @@ pub enum Power {
     ZacharyHudson,
     #[serde(rename = "Zemina Torval")]
     ZeminaTorval,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(Power);
+serde_with_other!(Power);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum PowerState {
     Exploited,
     Fortified,
@@ -103,6 +116,11 @@ pub enum PowerState {
     Prepared,
     /// Legacy: "Turmoil" was used in the past. Leave it here for backwards compatibility.
     Turmoil,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(PowerState);
+serde_with_other!(PowerState);
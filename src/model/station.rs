@@ -8,7 +8,7 @@ use super::dec::{date_format, date_format_opt};
 use super::RootEntry;
 
 use super::util::DisplayViaSerde;
-use crate::display_via_serde;
+use crate::{display_via_serde, serde_with_other};
 
 use serde;
 
@@ -80,6 +80,7 @@ pub struct Commodity {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum OtherService {
     #[serde(rename = "Black Market")]
     BlackMarket,
@@ -116,9 +117,14 @@ pub enum OtherService {
     #[serde(rename = "Fleet carrier vendor")]
     FleetCarrierVendor,
     Bartender,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(OtherService);
+serde_with_other!(OtherService);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -170,6 +176,7 @@ pub struct StationBody {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, VariantCount)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
 #[serde(deny_unknown_fields)]
+#[serde(remote = "Self")]
 pub enum StationType {
     // Orbital Large
     #[serde(rename = "Ocellus Starport")]
@@ -194,9 +201,14 @@ pub enum StationType {
     // Fleet Carrier
     #[serde(rename = "Fleet Carrier")]
     FleetCarrier,
+    /// A value unknown to this model, only produced by lenient parsing.
+    #[serde(skip)]
+    #[strum(disabled)]
+    Other(String),
 }
 
 display_via_serde!(StationType);
+serde_with_other!(StationType);
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "type_hash", derive(type_hash::TypeHash))]
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use chrono::{DateTime, Utc};
@@ -98,11 +101,122 @@ impl System for SystemWithoutCoordinates {
     }
 }
 
+impl SystemWithoutCoordinates {
+    /// EDSM's estimate, or else the boxel of the address or procedural name.
+    ///
+    /// Names are only used when the system has no address, and only in sectors learned in
+    /// `sectors`.
+    pub fn estimate_coords(&self, sectors: &SectorLocations) -> Option<EstimatedCoords> {
+        if let Some(estimate) = &self.estimated_coordinates {
+            return Some(estimate.clone());
+        }
+        if let Some(address) = self.address() {
+            return Some(address.boxel_bounds().estimated_coords());
+        }
+        let bounds = sectors.boxel_bounds(&self.procgen_name()?)?;
+        Some(bounds.estimated_coords())
+    }
+
+    /// Converts to a [`LocatedSystem`] at the [estimated coordinates](Self::estimate_coords).
+    pub fn into_located(self, sectors: &SectorLocations) -> Option<LocatedSystem> {
+        let estimate = self.estimate_coords(sectors)?;
+        Some(LocatedSystem {
+            id: self.id,
+            coords: estimate.coords(),
+            precision: Some(estimate.precision),
+            id64: self.id64,
+            name: self.name,
+            date: self.date,
+        })
+    }
+}
+
 pub trait System {
     fn id(&self) -> u64;
     fn id64(&self) -> Option<u64>;
     fn name(&self) -> &str;
     fn date(&self) -> DateTime<Utc>;
+
+    fn address(&self) -> Option<Id64> {
+        self.id64().map(Id64)
+    }
+
+    fn procgen_name(&self) -> Option<ProcGenName> {
+        ProcGenName::parse(self.name())
+    }
+}
+
+/// Entry with known coordinates.
+pub trait HasCoords {
+    fn coords(&self) -> Coords;
+}
+
+impl<T: HasCoords> HasCoords for &T {
+    fn coords(&self) -> Coords {
+        (*self).coords()
+    }
+}
+
+impl HasCoords for SystemWithCoordinates {
+    fn coords(&self) -> Coords {
+        self.coords
+    }
+}
+
+/// System with either known or estimated coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocatedSystem {
+    pub id: u64,
+    // Attributes
+    pub coords: Coords,
+    /// `None` if `coords` are known, otherwise the radius around `coords` the system is within.
+    pub precision: Option<f32>,
+    pub id64: Option<u64>,
+    pub name: String,
+    // Metadata
+    #[serde(with = "date_format")]
+    pub date: DateTime<Utc>,
+}
+
+impl LocatedSystem {
+    pub fn is_estimated(&self) -> bool {
+        self.precision.is_some()
+    }
+}
+
+impl From<SystemWithCoordinates> for LocatedSystem {
+    fn from(s: SystemWithCoordinates) -> LocatedSystem {
+        LocatedSystem {
+            id: s.id,
+            coords: s.coords,
+            precision: None,
+            id64: s.id64,
+            name: s.name,
+            date: s.date,
+        }
+    }
+}
+
+impl HasCoords for LocatedSystem {
+    fn coords(&self) -> Coords {
+        self.coords
+    }
+}
+
+impl System for LocatedSystem {
+    fn id(&self) -> u64 {
+        self.id
+    }
+    fn id64(&self) -> Option<u64> {
+        self.id64
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn date(&self) -> DateTime<Utc> {
+        self.date
+    }
 }
 
 // Field Type
@@ -232,3 +346,540 @@ pub struct EstimatedCoords {
     pub z: f32,
     pub precision: f32,
 }
+
+impl EstimatedCoords {
+    pub fn coords(&self) -> Coords {
+        Coords {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+// System Address
+
+/// Lower corner of the sector grid.
+pub const GALAXY_ORIGIN: Coords = Coords {
+    x: -49985.0,
+    y: -40985.0,
+    z: -24105.0,
+};
+
+/// Edge length of a sector in light years.
+pub const SECTOR_SIZE: f32 = 1280.0;
+
+/// Elite: Dangerous system address.
+///
+/// From the least significant bit, an address is laid out as:
+///
+/// | Field            | Bits                  |
+/// |------------------|-----------------------|
+/// | mass code        | 3                     |
+/// | boxel z          | 7 - mass code         |
+/// | sector z         | 7                     |
+/// | boxel y          | 7 - mass code         |
+/// | sector y         | 6                     |
+/// | boxel x          | 7 - mass code         |
+/// | sector x         | 7                     |
+/// | system index     | 11 + 3 * mass code    |
+/// | body ID          | 9                     |
+///
+/// The body ID part is zero for systems, and the address of a body is its system's address plus
+/// its body ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Id64(pub u64);
+
+impl Id64 {
+    const ADDRESS_BITS: u32 = 55;
+    const BODY_ID_BITS: u32 = 9;
+
+    pub fn from_parts(
+        mass_code: MassCode,
+        sector: SectorCoords,
+        boxel: BoxelCoords,
+        system_index: u64,
+    ) -> Option<Id64> {
+        let mc = mass_code as u32;
+        let boxel_bits = 7 - mc;
+        let index_bits = 11 + 3 * mc;
+
+        let boxel_max = 1u8 << boxel_bits;
+        if sector.x >= 128 || sector.y >= 64 || sector.z >= 128 {
+            return None;
+        }
+        if boxel.x >= boxel_max || boxel.y >= boxel_max || boxel.z >= boxel_max {
+            return None;
+        }
+        if system_index >= 1 << index_bits {
+            return None;
+        }
+
+        let fields = [
+            (mc as u64, 3),
+            (boxel.z as u64, boxel_bits),
+            (sector.z as u64, 7),
+            (boxel.y as u64, boxel_bits),
+            (sector.y as u64, 6),
+            (boxel.x as u64, boxel_bits),
+            (sector.x as u64, 7),
+            (system_index, index_bits),
+        ];
+
+        let mut v = 0;
+        let mut shift = 0;
+        for (field, bits) in fields {
+            v |= field << shift;
+            shift += bits;
+        }
+        Some(Id64(v))
+    }
+
+    /// Address of the body `body_id` in this system.
+    ///
+    /// Returns `None` if `body_id` does not fit in 9 bits.
+    pub fn with_body_id(self, body_id: u64) -> Option<Id64> {
+        if body_id >= 1 << Self::BODY_ID_BITS {
+            return None;
+        }
+        Some(Id64(self.system().0 | body_id << Self::ADDRESS_BITS))
+    }
+
+    /// Address of the system, with the body ID cleared.
+    pub fn system(self) -> Id64 {
+        Id64(self.bits(0, Self::ADDRESS_BITS))
+    }
+
+    pub fn body_id(self) -> u64 {
+        self.bits(Self::ADDRESS_BITS, Self::BODY_ID_BITS)
+    }
+
+    pub fn mass_code(self) -> MassCode {
+        MassCode::from_u8((self.0 & 0b111) as u8).expect("3 bits are always a mass code")
+    }
+
+    pub fn sector(self) -> SectorCoords {
+        let bb = self.boxel_bits();
+        SectorCoords {
+            x: self.bits(3 + 3 * bb + 13, 7) as u8,
+            y: self.bits(3 + 2 * bb + 7, 6) as u8,
+            z: self.bits(3 + bb, 7) as u8,
+        }
+    }
+
+    pub fn boxel(self) -> BoxelCoords {
+        let bb = self.boxel_bits();
+        BoxelCoords {
+            x: self.bits(3 + 2 * bb + 13, bb) as u8,
+            y: self.bits(3 + bb + 7, bb) as u8,
+            z: self.bits(3, bb) as u8,
+        }
+    }
+
+    /// Index of the system in its boxel.
+    pub fn system_index(self) -> u64 {
+        let bb = self.boxel_bits();
+        self.bits(3 + 3 * bb + 20, 11 + 3 * self.mass_code() as u32)
+    }
+
+    pub fn boxel_bounds(self) -> BoxelBounds {
+        BoxelBounds::new(self.mass_code(), self.sector(), self.boxel())
+    }
+
+    /// Lower corner of the boxel.
+    pub fn boxel_origin(self) -> Coords {
+        self.boxel_bounds().min
+    }
+
+    /// Approximate coordinates of the system: the centre of its boxel.
+    pub fn boxel_center(self) -> Coords {
+        self.boxel_bounds().center()
+    }
+
+    fn boxel_bits(self) -> u32 {
+        7 - self.mass_code() as u32
+    }
+
+    fn bits(self, offset: u32, len: u32) -> u64 {
+        (self.0 >> offset) & ((1 << len) - 1)
+    }
+}
+
+impl From<u64> for Id64 {
+    fn from(v: u64) -> Id64 {
+        Id64(v)
+    }
+}
+
+impl From<Id64> for u64 {
+    fn from(v: Id64) -> u64 {
+        v.0
+    }
+}
+
+impl fmt::Display for Id64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Size class of a boxel, written as `a` to `h` in procedural system names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MassCode {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+impl MassCode {
+    pub fn from_u8(v: u8) -> Option<MassCode> {
+        let mc = match v {
+            0 => MassCode::A,
+            1 => MassCode::B,
+            2 => MassCode::C,
+            3 => MassCode::D,
+            4 => MassCode::E,
+            5 => MassCode::F,
+            6 => MassCode::G,
+            7 => MassCode::H,
+            _ => return None,
+        };
+        Some(mc)
+    }
+
+    pub fn from_letter(c: char) -> Option<MassCode> {
+        if c.is_ascii_lowercase() {
+            MassCode::from_u8(c as u8 - b'a')
+        } else {
+            None
+        }
+    }
+
+    pub fn letter(self) -> char {
+        (b'a' + self as u8) as char
+    }
+
+    /// Edge length of a boxel in light years.
+    pub fn boxel_size(self) -> f32 {
+        SECTOR_SIZE / (1 << (7 - self as u32)) as f32
+    }
+
+    /// Number of boxels along each axis of a sector.
+    pub fn boxels_per_sector(self) -> u8 {
+        1 << (7 - self as u32)
+    }
+}
+
+impl fmt::Display for MassCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SectorCoords {
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+}
+
+impl SectorCoords {
+    /// Returns the sector containing `coords`, or `None` if it is outside the sector grid.
+    pub fn containing(coords: Coords) -> Option<SectorCoords> {
+        let rel = (coords - GALAXY_ORIGIN) / SECTOR_SIZE;
+        let axis = |v: f32, n: f32| {
+            if (0.0..n).contains(&v) {
+                Some(v as u8)
+            } else {
+                None
+            }
+        };
+        Some(SectorCoords {
+            x: axis(rel.x, 128.0)?,
+            y: axis(rel.y, 64.0)?,
+            z: axis(rel.z, 128.0)?,
+        })
+    }
+
+    /// Lower corner of the sector.
+    pub fn origin(self) -> Coords {
+        GALAXY_ORIGIN
+            + Coords {
+                x: self.x as f32,
+                y: self.y as f32,
+                z: self.z as f32,
+            } * SECTOR_SIZE
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoxelCoords {
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+}
+
+/// Axis-aligned box of a boxel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxelBounds {
+    /// Lower corner.
+    pub min: Coords,
+    /// Edge length in light years.
+    pub size: f32,
+}
+
+impl BoxelBounds {
+    pub fn new(mass_code: MassCode, sector: SectorCoords, boxel: BoxelCoords) -> BoxelBounds {
+        let size = mass_code.boxel_size();
+        let offset = Coords {
+            x: boxel.x as f32,
+            y: boxel.y as f32,
+            z: boxel.z as f32,
+        } * size;
+        BoxelBounds {
+            min: sector.origin() + offset,
+            size,
+        }
+    }
+
+    /// Upper corner.
+    pub fn max(&self) -> Coords {
+        self.min
+            + Coords {
+                x: self.size,
+                y: self.size,
+                z: self.size,
+            }
+    }
+
+    pub fn center(&self) -> Coords {
+        self.min
+            + Coords {
+                x: self.size,
+                y: self.size,
+                z: self.size,
+            } / 2.0
+    }
+
+    pub fn contains(&self, coords: Coords) -> bool {
+        let (min, max) = (self.min, self.max());
+        (min.x..=max.x).contains(&coords.x)
+            && (min.y..=max.y).contains(&coords.y)
+            && (min.z..=max.z).contains(&coords.z)
+    }
+
+    /// Distance from `coords` to the nearest point of the box, 0 inside it.
+    pub fn dist(&self, coords: Coords) -> f32 {
+        let (min, max) = (self.min, self.max());
+        let nearest = Coords {
+            x: coords.x.clamp(min.x, max.x),
+            y: coords.y.clamp(min.y, max.y),
+            z: coords.z.clamp(min.z, max.z),
+        };
+        coords.dist(nearest)
+    }
+
+    /// Estimate in the form EDSM uses: the centre, with half the diagonal as precision.
+    pub fn estimated_coords(&self) -> EstimatedCoords {
+        let c = self.center();
+        EstimatedCoords {
+            x: c.x,
+            y: c.y,
+            z: c.z,
+            precision: (self.size * 3f32.sqrt() / 2.0).round(),
+        }
+    }
+
+    /// Returns `true` if the sphere of the estimate touches the box.
+    pub fn agrees_with(&self, estimate: &EstimatedCoords) -> bool {
+        self.dist(estimate.coords()) <= estimate.precision
+    }
+}
+
+// Procedural Names
+
+/// Name of a procedurally generated system, such as `Dryoi Pri JP-I c12-1459`.
+///
+/// The letters and `n1` number the boxel in its sector, and `n2` is the
+/// [system index](Id64::system_index). `n1` is `None` if the name omits it, which the game does
+/// when it is 0.
+///
+/// Boxels of hand-authored sectors (e.g. `Col 285 Sector`) are numbered from the corner of the
+/// hand-authored sector rather than the sector grid, so [`ProcGenName::boxel`] is only meaningful
+/// for sectors with generated names.
+///
+/// Sector names are not decoded; positions of sectors come from [`SectorLocations`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProcGenName {
+    pub sector: String,
+    /// Letters of the boxel ID, e.g. `['J', 'P', 'I']` for `JP-I`.
+    pub boxel_id: [char; 3],
+    pub mass_code: MassCode,
+    pub n1: Option<u64>,
+    pub n2: u64,
+}
+
+impl ProcGenName {
+    /// Parses a procedural name, or returns `None` for other names.
+    pub fn parse(name: &str) -> Option<ProcGenName> {
+        let mut words = name.rsplitn(3, ' ');
+        let numbers = words.next()?;
+        let letters = words.next()?;
+        let sector = words.next()?;
+
+        let letters: Vec<char> = letters.chars().collect();
+        let boxel_id = match letters[..] {
+            [a, b, '-', c] if [a, b, c].iter().all(char::is_ascii_uppercase) => [a, b, c],
+            _ => return None,
+        };
+
+        let mut chars = numbers.chars();
+        let mass_code = MassCode::from_letter(chars.next()?)?;
+        let numbers = chars.as_str();
+        let (n1, n2) = match numbers.split_once('-') {
+            Some((n1, n2)) => (Some(parse_number(n1)?), parse_number(n2)?),
+            None => (None, parse_number(numbers)?),
+        };
+
+        Some(ProcGenName {
+            sector: sector.to_owned(),
+            boxel_id,
+            mass_code,
+            n1,
+            n2,
+        })
+    }
+
+    /// Name of the system `system_index` in `boxel` of a sector.
+    pub fn new(
+        sector: impl Into<String>,
+        mass_code: MassCode,
+        boxel: BoxelCoords,
+        system_index: u64,
+    ) -> ProcGenName {
+        let n = boxel.x as u64 + boxel.y as u64 * 128 + boxel.z as u64 * 128 * 128;
+        let letter = |i: u64| (b'A' + (n / 26u64.pow(i as u32) % 26) as u8) as char;
+        ProcGenName {
+            sector: sector.into(),
+            boxel_id: [letter(0), letter(1), letter(2)],
+            mass_code,
+            n1: Some(n / 26u64.pow(3)).filter(|&n1| n1 != 0),
+            n2: system_index,
+        }
+    }
+
+    /// Number of the boxel in its sector.
+    pub fn boxel_number(&self) -> u64 {
+        let [a, b, c] = self.boxel_id.map(|l| (l as u8 - b'A') as u64);
+        a + b * 26 + c * 26 * 26 + self.n1.unwrap_or(0) * 26 * 26 * 26
+    }
+
+    /// Position of the boxel in its sector, or `None` if it is outside the sector.
+    pub fn boxel(&self) -> Option<BoxelCoords> {
+        let n = self.boxel_number();
+        let boxel = BoxelCoords {
+            x: (n % 128) as u8,
+            y: (n / 128 % 128) as u8,
+            z: u8::try_from(n / (128 * 128)).ok()?,
+        };
+        let max = self.mass_code.boxels_per_sector();
+        if boxel.x < max && boxel.y < max && boxel.z < max {
+            Some(boxel)
+        } else {
+            None
+        }
+    }
+
+    pub fn system_index(&self) -> u64 {
+        self.n2
+    }
+
+    /// Returns `true` if `id64` has the boxel and system index of this name.
+    pub fn matches(&self, id64: Id64) -> bool {
+        self.mass_code == id64.mass_code()
+            && self.boxel() == Some(id64.boxel())
+            && self.n2 == id64.system_index()
+    }
+}
+
+/// Positions of procedurally named sectors, learned from systems with known addresses.
+///
+/// Sector names are not decoded, so only names in sectors learned with [`add`](Self::add) can be
+/// located.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SectorLocations(HashMap<String, SectorCoords>);
+
+impl SectorLocations {
+    pub fn new() -> SectorLocations {
+        SectorLocations::default()
+    }
+
+    /// Learns the sector of `system` if it has a procedural name that agrees with its address.
+    ///
+    /// Returns `true` if the sector was learned.
+    pub fn add<S: System>(&mut self, system: &S) -> bool {
+        match (system.procgen_name(), system.address()) {
+            (Some(name), Some(address)) if name.matches(address) => {
+                self.0.insert(name.sector, address.sector());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn get(&self, sector: &str) -> Option<SectorCoords> {
+        self.0.get(sector).copied()
+    }
+
+    /// Bounds of the boxel of `name`, or `None` if its sector has not been learned.
+    pub fn boxel_bounds(&self, name: &ProcGenName) -> Option<BoxelBounds> {
+        Some(BoxelBounds::new(
+            name.mass_code,
+            self.get(&name.sector)?,
+            name.boxel()?,
+        ))
+    }
+
+    /// Address of the system `name`, or `None` if its sector has not been learned.
+    pub fn id64(&self, name: &ProcGenName) -> Option<Id64> {
+        Id64::from_parts(
+            name.mass_code,
+            self.get(&name.sector)?,
+            name.boxel()?,
+            name.n2,
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn parse_number(s: &str) -> Option<u64> {
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+impl fmt::Display for ProcGenName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c] = self.boxel_id;
+        write!(f, "{} {}{}-{} {}", self.sector, a, b, c, self.mass_code)?;
+        if let Some(n1) = self.n1 {
+            write!(f, "{}-", n1)?;
+        }
+        write!(f, "{}", self.n2)
+    }
+}
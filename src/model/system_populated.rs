@@ -6,7 +6,7 @@ use super::bgs;
 use super::body;
 use super::dec::date_format;
 use super::station;
-use super::system;
+use super::system::{self, HasCoords};
 use super::RootEntry;
 
 // Main Type
@@ -51,6 +51,12 @@ impl RootEntry for SystemPopulated {
     }
 }
 
+impl HasCoords for SystemPopulated {
+    fn coords(&self) -> system::Coords {
+        self.coords
+    }
+}
+
 // Field Type
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -22,3 +22,74 @@ macro_rules! display_via_serde {
         }
     };
 }
+
+/// Implements `Serialize` and `Deserialize` for an enum derived with `#[serde(remote = "Self")]`
+/// and a `#[serde(skip)]` `Other` variant.
+///
+/// `Other` is written as the unknown value itself. It is only read back in lenient mode, see
+/// [`crate::model::lenient`]; strict parsing rejects unknown values.
+///
+/// With `tagged`, `Other(tag, value)` is written as `{tag: value}`.
+#[macro_export]
+macro_rules! serde_with_other {
+    ($t:ident) => {
+        impl serde::Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    $t::Other(value) => serializer.serialize_str(value),
+                    _ => $t::serialize(self, serializer),
+                }
+            }
+        }
+
+        $crate::serde_with_other!(@deserialize $t, value => {
+            value.as_str().map(|s| $t::Other(s.to_owned()))
+        });
+    };
+    ($t:ident, tagged) => {
+        impl serde::Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                use serde::ser::SerializeMap;
+
+                match self {
+                    $t::Other(tag, value) => {
+                        let mut map = serializer.serialize_map(Some(1))?;
+                        map.serialize_entry(tag, value)?;
+                        map.end()
+                    }
+                    _ => $t::serialize(self, serializer),
+                }
+            }
+        }
+
+        $crate::serde_with_other!(@deserialize $t, value => {
+            let entry = value.as_object().filter(|m| m.len() == 1);
+            entry
+                .and_then(|m| m.iter().next())
+                .and_then(|(tag, v)| Some($t::Other(tag.clone(), v.as_u64()?)))
+        });
+    };
+    (@deserialize $t:ident, $value:ident => $other:block) => {
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                if !$crate::model::lenient::accepts_other() {
+                    return $t::deserialize(deserializer);
+                }
+                let $value = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+                match $t::deserialize(&$value) {
+                    Ok(known) => Ok(known),
+                    Err(e) => $other.ok_or_else(|| serde::de::Error::custom(e)),
+                }
+            }
+        }
+    };
+}
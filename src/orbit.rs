@@ -0,0 +1,197 @@
+//! Keplerian orbits from body orbital elements.
+//!
+//! EDSM does not give the ascending node or the mean anomaly, so [`Orbit::from_body`] sets both to 0.
+
+use std::f64::consts::PI;
+use std::ops::{Add, Sub};
+
+use chrono::{DateTime, Utc};
+
+use crate::model::body::{Body, BodyT};
+use crate::system_tree::SystemTree;
+
+/// Light seconds per astronomical unit.
+pub const LS_PER_AU: f64 = 499.004_783_836;
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// Position in AU, in the reference plane of the parent.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector {
+    pub fn abs(self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    pub fn dist(self, other: Vector) -> f64 {
+        (self - other).abs()
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+    fn add(self, other: Vector) -> Vector {
+        Vector {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+    fn sub(self, other: Vector) -> Vector {
+        Vector {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orbit {
+    /// In AU.
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    /// In degrees.
+    pub inclination: f64,
+    /// In degrees.
+    pub arg_of_periapsis: f64,
+    /// Longitude of the ascending node in degrees.
+    pub ascending_node: f64,
+    /// In days.
+    pub period: f64,
+    /// Mean anomaly at `epoch` in degrees.
+    pub mean_anomaly_at_epoch: f64,
+    pub epoch: DateTime<Utc>,
+}
+
+impl Orbit {
+    /// Orbit of a star or planet, if EDSM has its semi-major axis and period.
+    ///
+    /// Missing angles and eccentricity are taken as 0, and the epoch as the Unix epoch.
+    pub fn from_body(body: &Body) -> Option<Orbit> {
+        let arg_of_periapsis = match body {
+            Body::Planet(p) => p.arg_of_periapsis,
+            Body::Star(s) => s.arg_of_periapsis,
+            Body::Unknown(_) => return None,
+        };
+        let period = body.orbital_period()? as f64;
+        if period == 0.0 {
+            return None;
+        }
+        Some(Orbit {
+            semi_major_axis: body.semi_major_axis()? as f64,
+            eccentricity: body.orbital_eccentricity().unwrap_or(0.0) as f64,
+            inclination: body.orbital_inclination().unwrap_or(0.0) as f64,
+            arg_of_periapsis: arg_of_periapsis.unwrap_or(0.0) as f64,
+            ascending_node: 0.0,
+            period,
+            mean_anomaly_at_epoch: 0.0,
+            epoch: DateTime::<Utc>::UNIX_EPOCH,
+        })
+    }
+
+    /// Closest distance to the parent in AU.
+    pub fn periapsis(&self) -> f64 {
+        self.semi_major_axis * (1.0 - self.eccentricity)
+    }
+
+    /// Farthest distance to the parent in AU.
+    pub fn apoapsis(&self) -> f64 {
+        self.semi_major_axis * (1.0 + self.eccentricity)
+    }
+
+    /// Mean anomaly at `t` in radians, in `[0, 2π)`.
+    pub fn mean_anomaly_at(&self, t: DateTime<Utc>) -> f64 {
+        let days = (t - self.epoch).num_milliseconds() as f64 / 1000.0 / SECONDS_PER_DAY;
+        let m = self.mean_anomaly_at_epoch.to_radians() + 2.0 * PI * days / self.period;
+        m.rem_euclid(2.0 * PI)
+    }
+
+    /// True anomaly at `t` in radians.
+    pub fn true_anomaly_at(&self, t: DateTime<Utc>) -> f64 {
+        let e = self.eccentricity;
+        let ea = eccentric_anomaly(self.mean_anomaly_at(t), e);
+        2.0 * ((1.0 + e).sqrt() * (ea / 2.0).sin()).atan2((1.0 - e).sqrt() * (ea / 2.0).cos())
+    }
+
+    /// Position relative to the parent at `t`.
+    pub fn position_at(&self, t: DateTime<Utc>) -> Vector {
+        let e = self.eccentricity;
+        let nu = self.true_anomaly_at(t);
+        let r = self.semi_major_axis * (1.0 - e * e) / (1.0 + e * nu.cos());
+
+        let (o, w, i) = (
+            self.ascending_node.to_radians(),
+            self.arg_of_periapsis.to_radians(),
+            self.inclination.to_radians(),
+        );
+        let u = w + nu;
+        Vector {
+            x: r * (o.cos() * u.cos() - o.sin() * u.sin() * i.cos()),
+            y: r * (o.sin() * u.cos() + o.cos() * u.sin() * i.cos()),
+            z: r * u.sin() * i.sin(),
+        }
+    }
+
+    /// Distance to the parent at `t` in AU.
+    pub fn distance_at(&self, t: DateTime<Utc>) -> f64 {
+        self.position_at(t).abs()
+    }
+}
+
+/// Solves Kepler's equation `M = E - e sin E` for `E`.
+fn eccentric_anomaly(m: f64, e: f64) -> f64 {
+    let mut ea = if e < 0.8 { m } else { PI };
+    for _ in 0..50 {
+        let step = (ea - e * ea.sin() - m) / (1.0 - e * ea.cos());
+        ea -= step;
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    ea
+}
+
+/// Position of `body_id` relative to the root of its tree at `t`.
+///
+/// Barycentres have no orbital elements in EDSM, so each one is placed at its parent.
+/// Returns `None` if a star or planet on the way has no orbit.
+pub fn position(tree: &SystemTree, body_id: u64, t: DateTime<Utc>) -> Option<Vector> {
+    let mut pos = Vector::default();
+    let mut node = tree.get(body_id)?;
+    let mut steps = 0;
+    while node.parent.is_some() {
+        if !node.is_barycentre() {
+            pos = pos + Orbit::from_body(node.body.as_ref()?)?.position_at(t);
+        }
+        node = tree.parent(node.body_id)?;
+        steps += 1;
+        if steps > tree.len() {
+            return None;
+        }
+    }
+    Some(pos)
+}
+
+/// Distance between two bodies of the same tree at `t` in AU.
+///
+/// See [`position`] for when this is `None`.
+pub fn distance(tree: &SystemTree, a: u64, b: u64, t: DateTime<Utc>) -> Option<f64> {
+    let root = |id: u64| match tree.ancestors(id)?.last() {
+        Some(r) => Some(*r),
+        None => Some(id),
+    };
+    if root(a)? != root(b)? {
+        return None;
+    }
+    Some(position(tree, a, t)?.dist(position(tree, b, t)?))
+}
@@ -0,0 +1,254 @@
+//! Parallel dump parsing on a rayon pool.
+//!
+//! A background thread decompresses the input and splits it into batches of raw entries,
+//! which are then parsed on a rayon pool owned by the reader, so consumers are free to use the
+//! global pool.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread;
+use std::vec;
+
+use anyhow::{Context, Result};
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::compression::decompress;
+use crate::model::RootEntry;
+use crate::reader::{RawEntries, RawEntry};
+
+const DEFAULT_BATCH_SIZE: usize = 1024;
+const DEFAULT_QUEUE_DEPTH: usize = 64;
+
+type Batch<T> = (usize, Vec<Result<T>>);
+
+pub struct ParallelReader<R> {
+    raw: RawEntries<R>,
+    batch_size: usize,
+    queue_depth: usize,
+    num_threads: usize,
+}
+
+impl ParallelReader<Box<dyn Read + Send>> {
+    /// Opens a dump file, decompressing it transparently.
+    pub fn open(path: impl AsRef<Path>) -> Result<ParallelReader<Box<dyn Read + Send>>> {
+        let path = path.as_ref();
+        let f = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        let r = decompress(f).with_context(|| format!("opening {}", path.display()))?;
+        Ok(ParallelReader::new(r))
+    }
+}
+
+impl<R: Read + Send + 'static> ParallelReader<R> {
+    pub fn new(r: R) -> ParallelReader<R> {
+        ParallelReader {
+            raw: RawEntries::new(r),
+            batch_size: DEFAULT_BATCH_SIZE,
+            queue_depth: DEFAULT_QUEUE_DEPTH,
+            num_threads: 0,
+        }
+    }
+
+    /// Number of entries parsed by a worker at once.
+    pub fn batch_size(mut self, batch_size: usize) -> ParallelReader<R> {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Number of batches buffered ahead of the parsers, and of parsed batches held back
+    /// in [`ParallelReader::ordered`] while an earlier one is still being parsed.
+    pub fn queue_depth(mut self, queue_depth: usize) -> ParallelReader<R> {
+        self.queue_depth = queue_depth.max(1);
+        self
+    }
+
+    /// Number of parser threads. By default, rayon chooses one per CPU.
+    pub fn num_threads(mut self, num_threads: usize) -> ParallelReader<R> {
+        self.num_threads = num_threads;
+        self
+    }
+
+    /// Parses entries in parallel and yields them in input order.
+    pub fn ordered<T: RootEntry>(self) -> ParallelEntries<T> {
+        self.spawn_parser().ordered(true)
+    }
+
+    /// Parses entries in parallel and yields them in completion order.
+    pub fn unordered<T: RootEntry>(self) -> ParallelEntries<T> {
+        self.spawn_parser().ordered(false)
+    }
+
+    /// Calls `f` for each entry in parallel. Stops at the first error.
+    pub fn par_for_each<T, F>(self, f: F) -> Result<()>
+    where
+        T: RootEntry,
+        F: Fn(T) + Sync + Send,
+    {
+        let pool = self.build_pool()?;
+        let batches = self.spawn_reader();
+        pool.install(|| {
+            batches.into_iter().par_bridge().try_for_each(|(_, batch)| {
+                for raw in batch {
+                    f(raw?.parse()?);
+                }
+                Ok(())
+            })
+        })
+    }
+
+    /// Folds entries in parallel. Stops at the first error.
+    ///
+    /// As in rayon, `identity` may be called many times, and `fold` results are combined by `reduce`
+    /// in no particular order.
+    pub fn par_fold<T, A, ID, F, RF>(self, identity: ID, fold: F, reduce: RF) -> Result<A>
+    where
+        T: RootEntry,
+        A: Send,
+        ID: Fn() -> A + Sync + Send,
+        F: Fn(A, T) -> A + Sync + Send,
+        RF: Fn(A, A) -> A + Sync + Send,
+    {
+        let pool = self.build_pool()?;
+        let batches = self.spawn_reader();
+        pool.install(|| {
+            batches
+                .into_iter()
+                .par_bridge()
+                .map(|(_, batch)| {
+                    batch
+                        .into_iter()
+                        .try_fold(identity(), |acc, raw| Ok(fold(acc, raw?.parse()?)))
+                })
+                .try_reduce(&identity, |a, b| Ok(reduce(a, b)))
+        })
+    }
+
+    fn build_pool(&self) -> Result<ThreadPool> {
+        ThreadPoolBuilder::new()
+            .num_threads(self.num_threads)
+            .thread_name(|i| format!("edsm-parser-{}", i))
+            .build()
+            .context("building parser thread pool")
+    }
+
+    fn spawn_reader(self) -> Receiver<Batch<RawEntry>> {
+        let (tx, rx) = sync_channel(self.queue_depth);
+        let batch_size = self.batch_size;
+        let mut raw = self.raw;
+
+        thread::spawn(move || {
+            for index in 0.. {
+                let batch: Vec<_> = raw.by_ref().take(batch_size).collect();
+                if batch.is_empty() || tx.send((index, batch)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        rx
+    }
+
+    /// Parses batches on a dedicated pool.
+    ///
+    /// A batch is only dispatched with a permit, returned by [`ParallelEntries`] when it takes a
+    /// batch out, so at most `queue_depth` batches are parsed or waiting to be taken at once and
+    /// the workers never block on a full channel.
+    fn spawn_parser<T: RootEntry>(self) -> ParallelEntries<T> {
+        let limit = self.queue_depth;
+        let (tx, rx) = sync_channel(limit);
+        let (permit_tx, permit_rx) = sync_channel(limit);
+        for _ in 0..limit {
+            let _ = permit_tx.send(());
+        }
+
+        let pool = match self.build_pool() {
+            Ok(pool) => pool,
+            Err(e) => {
+                let _ = tx.send((0, vec![Err(e)]));
+                return ParallelEntries::new(rx, permit_tx);
+            }
+        };
+        let batches = self.spawn_reader();
+
+        thread::spawn(move || {
+            for (index, batch) in batches {
+                if permit_rx.recv().is_err() {
+                    break;
+                }
+                let tx = tx.clone();
+                pool.spawn(move || {
+                    let parsed = batch
+                        .into_iter()
+                        .map(|raw| raw.and_then(|raw| raw.parse()))
+                        .collect();
+                    let _ = tx.send((index, parsed));
+                });
+            }
+        });
+
+        ParallelEntries::new(rx, permit_tx)
+    }
+}
+
+/// Iterator over entries parsed by [`ParallelReader`].
+pub struct ParallelEntries<T> {
+    rx: Receiver<Batch<T>>,
+    permits: SyncSender<()>,
+    ordered: bool,
+    next_index: usize,
+    pending: BTreeMap<usize, Vec<Result<T>>>,
+    current: vec::IntoIter<Result<T>>,
+}
+
+impl<T> ParallelEntries<T> {
+    fn new(rx: Receiver<Batch<T>>, permits: SyncSender<()>) -> ParallelEntries<T> {
+        ParallelEntries {
+            rx,
+            permits,
+            ordered: false,
+            next_index: 0,
+            pending: BTreeMap::new(),
+            current: Vec::new().into_iter(),
+        }
+    }
+
+    fn ordered(mut self, ordered: bool) -> ParallelEntries<T> {
+        self.ordered = ordered;
+        self
+    }
+
+    fn take(&mut self, batch: Vec<Result<T>>) {
+        self.current = batch.into_iter();
+        let _ = self.permits.try_send(());
+    }
+}
+
+impl<T> Iterator for ParallelEntries<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        loop {
+            if let Some(entry) = self.current.next() {
+                return Some(entry);
+            }
+
+            if self.ordered {
+                if let Some(batch) = self.pending.remove(&self.next_index) {
+                    self.next_index += 1;
+                    self.take(batch);
+                    continue;
+                }
+            }
+
+            let (index, batch) = self.rx.recv().ok()?;
+            if self.ordered {
+                self.pending.insert(index, batch);
+            } else {
+                self.take(batch);
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Physical properties derived from the raw body fields, in SI units.
+//!
+//! Incomplete records with a zero radius or mass give infinite or NaN results.
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::model::body::{Body, BodyT, Planet, Star};
+use crate::system_tree::SystemTree;
+
+/// Gravitational constant in m³/(kg s²).
+pub const G: f64 = 6.674_30e-11;
+/// Standard gravity in m/s².
+pub const STANDARD_GRAVITY: f64 = 9.806_65;
+/// Stefan–Boltzmann constant in W/(m² K⁴).
+pub const STEFAN_BOLTZMANN: f64 = 5.670_374_419e-8;
+
+pub const EARTH_MASS: f64 = 5.972_2e24;
+pub const SOLAR_MASS: f64 = 1.988_47e30;
+pub const SOLAR_RADIUS: f64 = 6.957e8;
+pub const SOLAR_LUMINOSITY: f64 = 3.828e26;
+pub const METERS_PER_AU: f64 = 1.495_978_707e11;
+pub const METERS_PER_LS: f64 = 299_792_458.0;
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+macro_rules! unit {
+    ($t:ident, $symbol:expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+        pub struct $t(pub f64);
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match f.precision() {
+                    Some(p) => write!(f, "{:.*} {}", p, self.0, $symbol),
+                    None => write!(f, "{} {}", self.0, $symbol),
+                }
+            }
+        }
+    };
+}
+
+unit!(Kilograms, "kg");
+unit!(Meters, "m");
+unit!(SquareMeters, "m²");
+unit!(KilogramsPerCubicMeter, "kg/m³");
+unit!(MetersPerSecond, "m/s");
+unit!(MetersPerSecondSquared, "m/s²");
+unit!(Watts, "W");
+
+impl Kilograms {
+    pub fn in_earth_masses(self) -> f64 {
+        self.0 / EARTH_MASS
+    }
+
+    pub fn in_solar_masses(self) -> f64 {
+        self.0 / SOLAR_MASS
+    }
+}
+
+impl Meters {
+    pub fn in_km(self) -> f64 {
+        self.0 / 1000.0
+    }
+
+    pub fn in_au(self) -> f64 {
+        self.0 / METERS_PER_AU
+    }
+
+    pub fn in_ls(self) -> f64 {
+        self.0 / METERS_PER_LS
+    }
+
+    pub fn from_au(au: f64) -> Meters {
+        Meters(au * METERS_PER_AU)
+    }
+
+    pub fn from_ls(ls: f64) -> Meters {
+        Meters(ls * METERS_PER_LS)
+    }
+}
+
+impl MetersPerSecondSquared {
+    /// In multiples of standard gravity, the unit of [`Planet::gravity`].
+    pub fn in_g(self) -> f64 {
+        self.0 / STANDARD_GRAVITY
+    }
+}
+
+impl MetersPerSecond {
+    pub fn in_km_per_s(self) -> f64 {
+        self.0 / 1000.0
+    }
+}
+
+impl Watts {
+    pub fn in_solar_luminosities(self) -> f64 {
+        self.0 / SOLAR_LUMINOSITY
+    }
+}
+
+/// A body with a mass and a radius.
+pub trait Physical {
+    fn mass(&self) -> Kilograms;
+    fn radius(&self) -> Meters;
+    /// In days, negative for retrograde rotation.
+    fn rotational_period(&self) -> Option<f64>;
+
+    /// Mean density.
+    fn density(&self) -> KilogramsPerCubicMeter {
+        let r = self.radius().0;
+        KilogramsPerCubicMeter(self.mass().0 / (4.0 / 3.0 * PI * r.powi(3)))
+    }
+
+    /// Gravity at the surface, computed from mass and radius.
+    fn surface_gravity(&self) -> MetersPerSecondSquared {
+        MetersPerSecondSquared(G * self.mass().0 / self.radius().0.powi(2))
+    }
+
+    fn escape_velocity(&self) -> MetersPerSecond {
+        MetersPerSecond((2.0 * G * self.mass().0 / self.radius().0).sqrt())
+    }
+
+    fn surface_area(&self) -> SquareMeters {
+        SquareMeters(4.0 * PI * self.radius().0.powi(2))
+    }
+
+    /// Speed of the surface at the equator due to rotation.
+    fn equatorial_speed(&self) -> Option<MetersPerSecond> {
+        let period = self.rotational_period()?.abs() * SECONDS_PER_DAY;
+        if period == 0.0 {
+            return None;
+        }
+        Some(MetersPerSecond(2.0 * PI * self.radius().0 / period))
+    }
+}
+
+impl Physical for Planet {
+    fn mass(&self) -> Kilograms {
+        Kilograms(self.earth_masses as f64 * EARTH_MASS)
+    }
+
+    fn radius(&self) -> Meters {
+        Meters(self.radius as f64 * 1000.0)
+    }
+
+    fn rotational_period(&self) -> Option<f64> {
+        self.rotational_period.map(f64::from)
+    }
+}
+
+impl Physical for Star {
+    fn mass(&self) -> Kilograms {
+        Kilograms(self.solar_masses as f64 * SOLAR_MASS)
+    }
+
+    fn radius(&self) -> Meters {
+        Meters(self.solar_radius as f64 * SOLAR_RADIUS)
+    }
+
+    fn rotational_period(&self) -> Option<f64> {
+        self.rotational_period.map(f64::from)
+    }
+}
+
+/// The star or planet as [`Physical`], `None` for unknown bodies.
+pub fn physical(body: &Body) -> Option<&dyn Physical> {
+    match body {
+        Body::Planet(p) => Some(p),
+        Body::Star(s) => Some(s),
+        Body::Unknown(_) => None,
+    }
+}
+
+/// Relative difference of the computed surface gravity to [`Planet::gravity`].
+///
+/// Returns `None` if EDSM has no gravity for the planet.
+pub fn gravity_discrepancy(planet: &Planet) -> Option<f64> {
+    let reported = planet.gravity? as f64;
+    if reported == 0.0 {
+        return None;
+    }
+    Some(planet.surface_gravity().in_g() / reported - 1.0)
+}
+
+/// Luminosity of a black body with the radius and surface temperature of `star`.
+pub fn luminosity(star: &Star) -> Watts {
+    let r = star.radius().0;
+    let t = star.surface_temperature as f64;
+    Watts(4.0 * PI * r * r * STEFAN_BOLTZMANN * t.powi(4))
+}
+
+/// Mass of a node of `tree`: the body's mass, or the summed masses orbiting a barycentre.
+///
+/// Returns `None` if a body on the way is missing or unknown.
+pub fn node_mass(tree: &SystemTree, body_id: u64) -> Option<Kilograms> {
+    node_mass_at(tree, body_id, 0)
+}
+
+fn node_mass_at(tree: &SystemTree, body_id: u64, depth: usize) -> Option<Kilograms> {
+    let node = tree.get(body_id)?;
+    if !node.is_barycentre() {
+        return Some(physical(node.body.as_ref()?)?.mass());
+    }
+    if depth > tree.len() {
+        return None;
+    }
+    let mut mass = 0.0;
+    for &child in &node.children {
+        mass += node_mass_at(tree, child, depth + 1)?.0;
+    }
+    Some(Kilograms(mass))
+}
+
+/// `body_id` as [`Physical`], the mass it orbits and its distance at periapsis.
+fn orbit_around_parent(tree: &SystemTree, body_id: u64) -> Option<(&dyn Physical, f64, f64)> {
+    let node = tree.get(body_id)?;
+    let body = node.body.as_ref()?;
+    let physical = physical(body)?;
+    let parent = tree.parent(body_id)?;
+
+    let mut parent_mass = node_mass(tree, parent.body_id)?.0;
+    if parent.is_barycentre() {
+        // The body is one of the masses of the barycentre.
+        parent_mass -= physical.mass().0;
+    }
+    let a = body.semi_major_axis()? as f64;
+    let e = body.orbital_eccentricity().unwrap_or(0.0) as f64;
+    let periapsis = Meters::from_au(a * (1.0 - e)).0;
+    (parent_mass > 0.0).then_some((physical, parent_mass, periapsis))
+}
+
+/// Radius of the Hill sphere of `body_id` around its parent, at periapsis.
+pub fn hill_sphere(tree: &SystemTree, body_id: u64) -> Option<Meters> {
+    let (body, parent_mass, periapsis) = orbit_around_parent(tree, body_id)?;
+    Some(Meters(
+        periapsis * (body.mass().0 / (3.0 * parent_mass)).cbrt(),
+    ))
+}
+
+/// Rigid-body Roche limit of `body_id` around its parent.
+///
+/// The body is torn apart by tides if it orbits closer than this.
+pub fn roche_limit(tree: &SystemTree, body_id: u64) -> Option<Meters> {
+    let (body, parent_mass, _) = orbit_around_parent(tree, body_id)?;
+    Some(Meters(
+        body.radius().0 * (2.0 * parent_mass / body.mass().0).cbrt(),
+    ))
+}
@@ -0,0 +1,399 @@
+//! Streaming readers for whole dump files.
+//!
+//! EDSM nightly dumps are a JSON array with one entry per line:
+//!
+//! ```text
+//! [
+//!     {"id":1, ...},
+//!     {"id":2, ...}
+//! ]
+//! ```
+//!
+//! [`RawEntries`] splits such a stream into the raw bytes of each entry, and [`DumpReader`]
+//! parses them into [`RootEntry`] values.
+//! Streams that do not follow the line-per-entry layout, from the first line that is not a
+//! single entry on, are handled by a slower fallback that accepts any formatting of a JSON array.
+//!
+//! [`open_dump`] opens a dump file and decompresses it according to its magic bytes.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::compression::decompress;
+use crate::model::lenient::Lenient;
+use crate::model::RootEntry;
+
+/// Opens a dump file, decompressing it transparently.
+///
+/// See [`crate::compression`] for the supported formats.
+pub fn open_dump<T: RootEntry>(
+    path: impl AsRef<Path>,
+) -> Result<DumpReader<T, Box<dyn Read + Send>>> {
+    let path = path.as_ref();
+    let f = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let r = decompress(f).with_context(|| format!("opening {}", path.display()))?;
+    Ok(DumpReader::new(r))
+}
+
+/// Position of an entry in the (decompressed) dump stream.
+///
+/// Errors returned by [`RawEntries`] and [`DumpReader`] carry this as context,
+/// so it can be recovered with `err.downcast_ref::<Position>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    /// 1-based line number of the first byte of the entry.
+    pub line: u64,
+    /// 0-based byte offset of the first byte of the entry.
+    pub offset: u64,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at line {} (byte offset {})", self.line, self.offset)
+    }
+}
+
+/// Raw JSON bytes of a single entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawEntry {
+    pub bytes: Vec<u8>,
+    pub position: Position,
+}
+
+impl RawEntry {
+    pub fn parse<T: RootEntry>(&self) -> Result<T> {
+        T::parse_dump_json(&self.bytes).context(self.position)
+    }
+
+    pub fn parse_lenient<T: RootEntry>(&self) -> Result<Lenient<T>> {
+        T::parse_dump_json_lenient(&self.bytes).context(self.position)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Unknown,
+    Lines,
+    Fallback,
+}
+
+/// Iterator over the raw entries of a dump stream.
+pub struct RawEntries<R> {
+    reader: BufReader<R>,
+    layout: Layout,
+    scanner: Scanner,
+    queue: VecDeque<RawEntry>,
+    line_buf: Vec<u8>,
+    line: u64,
+    offset: u64,
+    finished: bool,
+}
+
+impl<R: Read> RawEntries<R> {
+    pub fn new(r: R) -> RawEntries<R> {
+        RawEntries {
+            reader: BufReader::new(r),
+            layout: Layout::Unknown,
+            scanner: Scanner::default(),
+            queue: VecDeque::new(),
+            line_buf: Vec::new(),
+            line: 0,
+            offset: 0,
+            finished: false,
+        }
+    }
+
+    fn fill_queue(&mut self) -> Result<()> {
+        while self.queue.is_empty() && !self.finished {
+            self.line_buf.clear();
+            let n = self
+                .reader
+                .read_until(b'\n', &mut self.line_buf)
+                .with_context(|| format!("reading line {}", self.line + 1))?;
+            if n == 0 {
+                self.finished = true;
+                self.check_complete()?;
+                break;
+            }
+
+            self.line += 1;
+            let pos = Position {
+                line: self.line,
+                offset: self.offset,
+            };
+            self.offset += n as u64;
+
+            match self.layout {
+                Layout::Unknown => {
+                    let line = self.line_buf.trim_ascii();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    if line == b"[" {
+                        self.layout = Layout::Lines;
+                    } else {
+                        self.layout = Layout::Fallback;
+                        self.scan_line(pos)?;
+                    }
+                }
+                Layout::Lines => self.split_line(pos)?,
+                Layout::Fallback => self.scan_line(pos)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn split_line(&mut self, pos: Position) -> Result<()> {
+        let raw = &self.line_buf;
+        let leading = raw.len() - raw.trim_ascii_start().len();
+        let line = raw.trim_ascii();
+        let line = line.strip_suffix(b",").unwrap_or(line);
+
+        if line.is_empty() {
+            return Ok(());
+        }
+        if line == b"]" {
+            self.finished = true;
+            return Ok(());
+        }
+        if !is_single_value(line) {
+            self.layout = Layout::Fallback;
+            self.scanner.state = ScanState::BetweenEntries;
+            return self.scan_line(pos);
+        }
+
+        self.queue.push_back(RawEntry {
+            bytes: line.to_vec(),
+            position: Position {
+                line: pos.line,
+                offset: pos.offset + leading as u64,
+            },
+        });
+        Ok(())
+    }
+
+    fn scan_line(&mut self, pos: Position) -> Result<()> {
+        let finished = self
+            .scanner
+            .feed(&self.line_buf, pos, &mut self.queue)
+            .context(pos)?;
+        if finished {
+            self.finished = true;
+        }
+        Ok(())
+    }
+
+    fn check_complete(&self) -> Result<()> {
+        let pos = Position {
+            line: self.line,
+            offset: self.offset,
+        };
+        match self.layout {
+            Layout::Unknown => Ok(()),
+            Layout::Lines => bail!("unexpected end of dump: missing closing ']' {}", pos),
+            Layout::Fallback if self.scanner.state == ScanState::Done => Ok(()),
+            Layout::Fallback => bail!("unexpected end of dump {}", pos),
+        }
+    }
+}
+
+impl<R: Read> Iterator for RawEntries<R> {
+    type Item = Result<RawEntry>;
+
+    fn next(&mut self) -> Option<Result<RawEntry>> {
+        if let Some(entry) = self.queue.pop_front() {
+            return Some(Ok(entry));
+        }
+        if self.finished {
+            return None;
+        }
+
+        if let Err(e) = self.fill_queue() {
+            self.finished = true;
+            self.queue.clear();
+            return Some(Err(e));
+        }
+
+        self.queue.pop_front().map(Ok)
+    }
+}
+
+/// Returns `true` if `line` is one object or array, closed on its last byte.
+fn is_single_value(line: &[u8]) -> bool {
+    if !matches!(line.first(), Some(b'{' | b'[')) {
+        return false;
+    }
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, &b) in line.iter().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i == line.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ScanState {
+    #[default]
+    BeforeArray,
+    BetweenEntries,
+    InEntry,
+    Done,
+}
+
+/// Splitter for arbitrarily formatted JSON arrays.
+#[derive(Debug, Default)]
+struct Scanner {
+    state: ScanState,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    entry: Vec<u8>,
+    entry_pos: Option<Position>,
+}
+
+impl Scanner {
+    /// Feeds one line. Returns `true` when the closing bracket of the array is found.
+    fn feed(&mut self, line: &[u8], pos: Position, queue: &mut VecDeque<RawEntry>) -> Result<bool> {
+        for (i, &b) in line.iter().enumerate() {
+            match self.state {
+                ScanState::BeforeArray => match b {
+                    b'[' => self.state = ScanState::BetweenEntries,
+                    b if b.is_ascii_whitespace() => {}
+                    b => bail!("expected '[' but found {:?}", b as char),
+                },
+                ScanState::BetweenEntries => match b {
+                    b',' => {}
+                    b']' => {
+                        self.state = ScanState::Done;
+                        return Ok(true);
+                    }
+                    b if b.is_ascii_whitespace() => {}
+                    b => {
+                        self.state = ScanState::InEntry;
+                        self.entry_pos = Some(Position {
+                            line: pos.line,
+                            offset: pos.offset + i as u64,
+                        });
+                        self.push(b, queue);
+                    }
+                },
+                ScanState::InEntry => {
+                    if self.depth == 0 && !self.in_string && (b == b',' || b == b']') {
+                        self.finish_entry(queue);
+                        if b == b']' {
+                            self.state = ScanState::Done;
+                            return Ok(true);
+                        }
+                    } else {
+                        self.push(b, queue);
+                    }
+                }
+                ScanState::Done => return Ok(true),
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn push(&mut self, b: u8, queue: &mut VecDeque<RawEntry>) {
+        self.entry.push(b);
+
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if b == b'\\' {
+                self.escaped = true;
+            } else if b == b'"' {
+                self.in_string = false;
+            }
+            return;
+        }
+
+        match b {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => {
+                self.depth = self.depth.saturating_sub(1);
+                if self.depth == 0 {
+                    self.finish_entry(queue);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish_entry(&mut self, queue: &mut VecDeque<RawEntry>) {
+        let bytes = std::mem::take(&mut self.entry);
+        let bytes = bytes.trim_ascii();
+        if let Some(position) = self.entry_pos.take() {
+            if !bytes.is_empty() {
+                queue.push_back(RawEntry {
+                    bytes: bytes.to_vec(),
+                    position,
+                });
+            }
+        }
+        self.state = ScanState::BetweenEntries;
+    }
+}
+
+/// Iterator over the parsed entries of a dump stream.
+///
+/// Parse errors do not stop the iteration; I/O errors and malformed arrays do.
+pub struct DumpReader<T, R> {
+    raw: RawEntries<R>,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: RootEntry, R: Read> DumpReader<T, R> {
+    pub fn new(r: R) -> DumpReader<T, R> {
+        DumpReader {
+            raw: RawEntries::new(r),
+            _type: PhantomData,
+        }
+    }
+
+    pub fn into_raw(self) -> RawEntries<R> {
+        self.raw
+    }
+}
+
+impl<T: RootEntry, R: Read> Iterator for DumpReader<T, R> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        let raw = match self.raw.next()? {
+            Ok(raw) => raw,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(raw.parse())
+    }
+}
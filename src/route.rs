@@ -0,0 +1,483 @@
+//! Jump-range route planning over system coordinates.
+//!
+//! [`StarMap`] holds the systems to route through, and [`RoutePlanner`] searches it with A*,
+//! treating every pair of systems within jump range as a jump.
+//! With a [`BoostMap`], jumps out of neutron star and white dwarf systems are supercharged.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hasher;
+
+use anyhow::{bail, Result};
+
+pub mod boost;
+pub mod carrier;
+
+pub use boost::{Boost, BoostMap};
+pub use carrier::{Carrier, CarrierPlanner};
+
+use crate::model::system::{Coords, HasCoords, System};
+use crate::spatial::{Point, SpatialIndex};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapSystem {
+    pub id: u64,
+    pub id64: Option<u64>,
+    pub name: String,
+    pub coords: Coords,
+}
+
+/// Systems to route through, indexed by position and name.
+#[derive(Debug, Clone, Default)]
+pub struct StarMap {
+    systems: Vec<MapSystem>,
+    index: SpatialIndex,
+    /// Hashes of lowercased names and the positions of their systems, sorted by hash.
+    by_name: Vec<(u64, usize)>,
+}
+
+impl StarMap {
+    pub fn new<S, I>(systems: I) -> StarMap
+    where
+        S: System + HasCoords,
+        I: IntoIterator<Item = S>,
+    {
+        let systems: Vec<_> = systems
+            .into_iter()
+            .map(|s| MapSystem {
+                id: s.id(),
+                id64: s.id64(),
+                name: s.name().to_owned(),
+                coords: s.coords(),
+            })
+            .collect();
+
+        // Points are keyed by position in `systems`.
+        let index = SpatialIndex::new(
+            systems
+                .iter()
+                .enumerate()
+                .map(|(i, s)| Point {
+                    id: i as u64,
+                    coords: s.coords,
+                })
+                .collect(),
+        );
+        let mut by_name: Vec<_> = systems
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (name_hash(&s.name), i))
+            .collect();
+        by_name.sort_by_key(|&(hash, _)| hash);
+
+        StarMap {
+            systems,
+            index,
+            by_name,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    pub fn systems(&self) -> &[MapSystem] {
+        &self.systems
+    }
+
+    /// Finds a system by name, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&MapSystem> {
+        self.find(name).map(|i| &self.systems[i])
+    }
+
+    pub fn nearest(&self, coords: Coords) -> Option<&MapSystem> {
+        self.nearest_index(coords).map(|i| &self.systems[i])
+    }
+
+    pub(crate) fn find(&self, name: &str) -> Option<usize> {
+        let hash = name_hash(name);
+        let start = self.by_name.partition_point(|&(h, _)| h < hash);
+        self.by_name[start..]
+            .iter()
+            .take_while(|&&(h, _)| h == hash)
+            .map(|&(_, i)| i)
+            .find(|&i| lowercase(&self.systems[i].name).eq(lowercase(name)))
+    }
+
+    pub(crate) fn nearest_index(&self, coords: Coords) -> Option<usize> {
+        self.index.nearest(coords, 1).first().map(|n| n.id as usize)
+    }
+
+    pub(crate) fn system(&self, i: usize) -> &MapSystem {
+        &self.systems[i]
+    }
+
+    /// Calls `f` with the systems within `radius` of system `i`, other than `i` itself, and their
+    /// distances.
+    pub(crate) fn neighbors(&self, i: usize, radius: f32, f: &mut dyn FnMut(usize, f32)) {
+        self.index
+            .for_each_within(self.systems[i].coords, radius, |n| {
+                if n.id as usize != i {
+                    f(n.id as usize, n.dist)
+                }
+            });
+    }
+
+    pub(crate) fn resolve(&self, waypoint: &Waypoint) -> Result<usize> {
+        match waypoint {
+            Waypoint::Name(name) => match self.find(name) {
+                Some(i) => Ok(i),
+                None => bail!("unknown system {:?}", name),
+            },
+            Waypoint::Coords(coords) => match self.nearest_index(*coords) {
+                Some(i) => Ok(i),
+                None => bail!("no systems in the map"),
+            },
+        }
+    }
+}
+
+fn lowercase(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.chars().flat_map(char::to_lowercase)
+}
+
+fn name_hash(name: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for c in lowercase(name) {
+        hasher.write_u32(c as u32);
+    }
+    hasher.finish()
+}
+
+/// Start or end of a route.
+///
+/// Coordinates are snapped to the nearest system in the map.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Waypoint {
+    Name(String),
+    Coords(Coords),
+}
+
+impl From<&str> for Waypoint {
+    fn from(name: &str) -> Waypoint {
+        Waypoint::Name(name.to_owned())
+    }
+}
+
+impl From<String> for Waypoint {
+    fn from(name: String) -> Waypoint {
+        Waypoint::Name(name)
+    }
+}
+
+impl From<Coords> for Waypoint {
+    fn from(coords: Coords) -> Waypoint {
+        Waypoint::Coords(coords)
+    }
+}
+
+/// What a route minimizes first. Ties are broken by the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Optimize {
+    #[default]
+    Jumps,
+    Distance,
+}
+
+/// Frame shift drive fuel use.
+///
+/// A jump of `d` ly uses `linear_constant * (d * mass / optimal_mass) ^ power_constant / 1000` t of
+/// fuel.
+/// The route assumes the ship refuels to the same mass before each jump, e.g. by scooping, since
+/// the systems dump does not say which stars are scoopable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuelModel {
+    /// Total ship mass in tonnes, including fuel and cargo.
+    pub mass: f32,
+    /// Optimal mass of the drive in tonnes.
+    pub optimal_mass: f32,
+    /// Maximum fuel per jump in tonnes.
+    pub max_fuel_per_jump: f32,
+    /// Depends on the drive rating, e.g. 12 for A.
+    pub linear_constant: f32,
+    /// Depends on the drive class, e.g. 2.45 for 5.
+    pub power_constant: f32,
+}
+
+impl FuelModel {
+    /// Fuel in tonnes used by a jump of `distance` ly.
+    pub fn fuel_for(&self, distance: f32) -> f32 {
+        self.linear_constant * (distance * self.mass / self.optimal_mass).powf(self.power_constant)
+            / 1000.0
+    }
+
+    /// Longest jump with the maximum fuel per jump.
+    pub fn max_range(&self) -> f32 {
+        self.optimal_mass / self.mass
+            * (self.max_fuel_per_jump * 1000.0 / self.linear_constant)
+                .powf(1.0 / self.power_constant)
+    }
+}
+
+pub struct RoutePlanner<'a> {
+    map: &'a StarMap,
+    jump_range: f32,
+    fuel: Option<FuelModel>,
+    optimize: Optimize,
+    boosts: Option<&'a BoostMap>,
+}
+
+impl<'a> RoutePlanner<'a> {
+    pub fn new(map: &'a StarMap, jump_range: f32) -> RoutePlanner<'a> {
+        RoutePlanner {
+            map,
+            jump_range,
+            fuel: None,
+            optimize: Optimize::default(),
+            boosts: None,
+        }
+    }
+
+    /// Supercharges jumps out of systems in `boosts`.
+    pub fn boosts(mut self, boosts: &'a BoostMap) -> RoutePlanner<'a> {
+        self.boosts = Some(boosts);
+        self
+    }
+
+    /// Limits jumps to the range of the fuel model and reports fuel use.
+    pub fn fuel(mut self, fuel: FuelModel) -> RoutePlanner<'a> {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    pub fn optimize(mut self, optimize: Optimize) -> RoutePlanner<'a> {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Longest unboosted jump, taking the fuel model into account.
+    pub fn effective_range(&self) -> f32 {
+        match &self.fuel {
+            Some(fuel) => self.jump_range.min(fuel.max_range()),
+            None => self.jump_range,
+        }
+    }
+
+    pub fn plan(&self, from: impl Into<Waypoint>, to: impl Into<Waypoint>) -> Result<Route> {
+        let start = self.map.resolve(&from.into())?;
+        let goal = self.map.resolve(&to.into())?;
+        let range = self.effective_range();
+        let max_range = range * self.boosts.map_or(1.0, |b| b.max_factor());
+        let path = search(self.map, start, goal, max_range, self.optimize, |i, f| {
+            let factor = self.boost_at(i).map_or(1.0, Boost::factor);
+            self.map.neighbors(i, range * factor, f)
+        })?;
+        Ok(self.route(&path))
+    }
+
+    fn boost_at(&self, i: usize) -> Option<Boost> {
+        let id64 = self.map.system(i).id64?;
+        self.boosts?.get(id64)
+    }
+
+    fn route(&self, path: &[usize]) -> Route {
+        let mut hops = Vec::with_capacity(path.len());
+        let mut prev: Option<(usize, Coords)> = None;
+        for &i in path {
+            let system = self.map.system(i).clone();
+            let distance = prev.map_or(0.0, |(_, p)| p.dist(system.coords));
+            let boost = prev.and_then(|(p, _)| self.boost_at(p));
+            let boosted = distance / boost.map_or(1.0, Boost::factor);
+            let fuel = self.fuel.map(|f| f.fuel_for(boosted));
+            prev = Some((i, system.coords));
+            hops.push(Hop {
+                system,
+                distance,
+                fuel,
+                boost,
+            });
+        }
+        Route { hops }
+    }
+}
+
+/// A* search from `start` to `goal`, where `neighbors` passes the jumps out of a system to its
+/// callback.
+///
+/// `range` is the longest possible jump, used to estimate the remaining number of jumps.
+pub(crate) fn search<F>(
+    map: &StarMap,
+    start: usize,
+    goal: usize,
+    range: f32,
+    optimize: Optimize,
+    mut neighbors: F,
+) -> Result<Vec<usize>>
+where
+    F: FnMut(usize, &mut dyn FnMut(usize, f32)),
+{
+    let goal_coords = map.system(goal).coords;
+    let estimate = |i: usize| {
+        let d = map.system(i).coords.dist(goal_coords) as f64;
+        let jumps = (d / range as f64).ceil();
+        Cost::new(optimize, jumps, d)
+    };
+
+    let mut best: HashMap<usize, Cost> = HashMap::new();
+    let mut came_from: HashMap<usize, usize> = HashMap::new();
+    let mut open = BinaryHeap::new();
+    best.insert(start, Cost::ZERO);
+    open.push(Open {
+        f: estimate(start),
+        g: Cost::ZERO,
+        node: start,
+    });
+
+    while let Some(Open { g, node, .. }) = open.pop() {
+        if node == goal {
+            let mut path = vec![goal];
+            let mut cur = goal;
+            while let Some(&prev) = came_from.get(&cur) {
+                path.push(prev);
+                cur = prev;
+            }
+            path.reverse();
+            return Ok(path);
+        }
+        if best.get(&node).is_some_and(|b| *b < g) {
+            continue;
+        }
+
+        neighbors(node, &mut |next, dist| {
+            let g2 = g + Cost::new(optimize, 1.0, dist as f64);
+            if best.get(&next).is_some_and(|b| *b <= g2) {
+                return;
+            }
+            best.insert(next, g2);
+            came_from.insert(next, node);
+            open.push(Open {
+                f: g2 + estimate(next),
+                g: g2,
+                node: next,
+            });
+        });
+    }
+
+    bail!(
+        "no route from {} to {} with {} ly jumps",
+        map.system(start).name,
+        map.system(goal).name,
+        range
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    /// Systems along the route, starting with the start system.
+    pub hops: Vec<Hop>,
+}
+
+impl Route {
+    pub fn jumps(&self) -> usize {
+        self.hops.len().saturating_sub(1)
+    }
+
+    /// Number of supercharged jumps.
+    pub fn boosted_jumps(&self) -> usize {
+        self.hops.iter().filter(|h| h.boost.is_some()).count()
+    }
+
+    pub fn total_distance(&self) -> f32 {
+        self.hops.iter().map(|h| h.distance).sum()
+    }
+
+    /// Total fuel in tonnes, if planned with a fuel model.
+    pub fn total_fuel(&self) -> Option<f32> {
+        self.hops.iter().map(|h| h.fuel).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hop {
+    pub system: MapSystem,
+    /// Distance from the previous system, 0 for the start.
+    pub distance: f32,
+    /// Fuel used by the jump to this system, if planned with a fuel model. 0 for the start.
+    pub fuel: Option<f32>,
+    /// Supercharge used for the jump to this system.
+    pub boost: Option<Boost>,
+}
+
+/// Lexicographic cost: the optimized quantity first, then the other.
+#[derive(Debug, Clone, Copy)]
+struct Cost(f64, f64);
+
+impl Cost {
+    const ZERO: Cost = Cost(0.0, 0.0);
+
+    fn new(optimize: Optimize, jumps: f64, distance: f64) -> Cost {
+        match optimize {
+            Optimize::Jumps => Cost(jumps, distance),
+            Optimize::Distance => Cost(distance, jumps),
+        }
+    }
+}
+
+impl std::ops::Add for Cost {
+    type Output = Cost;
+    fn add(self, other: Cost) -> Cost {
+        Cost(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl PartialEq for Cost {
+    fn eq(&self, other: &Cost) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cost {}
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Cost) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cost {
+    fn cmp(&self, other: &Cost) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.total_cmp(&other.1))
+    }
+}
+
+/// Open set entry, ordered so that the max-heap pops the lowest `f`.
+struct Open {
+    f: Cost,
+    g: Cost,
+    node: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Open) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Open) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Open) -> Ordering {
+        other.f.cmp(&self.f).then(other.node.cmp(&self.node))
+    }
+}
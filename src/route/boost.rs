@@ -0,0 +1,99 @@
+//! FSD supercharge at neutron stars and white dwarfs.
+
+use std::collections::HashMap;
+
+use crate::model::body::{Body, BodyT, StarClass, StarSubType};
+
+/// Jump range boost from scooping a star's jet cone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Boost {
+    WhiteDwarf,
+    Neutron,
+}
+
+impl Boost {
+    pub fn of_star(sub_type: StarSubType) -> Option<Boost> {
+        match sub_type {
+            StarSubType::NeutronStar => Some(Boost::Neutron),
+            s if s.filter_star_class() == Some(StarClass::WhiteDwarfStars) => {
+                Some(Boost::WhiteDwarf)
+            }
+            _ => None,
+        }
+    }
+
+    /// Jump range multiplier.
+    pub fn factor(self) -> f32 {
+        match self {
+            Boost::WhiteDwarf => 1.5,
+            Boost::Neutron => 4.0,
+        }
+    }
+}
+
+/// Best boost available in each system, keyed by system address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoostMap {
+    systems: HashMap<u64, Boost>,
+    main_star_only: bool,
+}
+
+impl BoostMap {
+    /// Map that counts any star of a system.
+    pub fn new() -> BoostMap {
+        BoostMap::default()
+    }
+
+    /// Map that only counts main stars, i.e. boosts available on arrival.
+    pub fn main_star_only() -> BoostMap {
+        BoostMap {
+            main_star_only: true,
+            ..BoostMap::default()
+        }
+    }
+
+    /// Records the boost of `body` if it is a boosting star.
+    ///
+    /// Returns `true` if the body was recorded.
+    pub fn add(&mut self, body: &Body) -> bool {
+        let Body::Star(star) = body else {
+            return false;
+        };
+        if self.main_star_only && !star.is_main_star {
+            return false;
+        }
+        let (Some(boost), Some(system)) =
+            (Boost::of_star(star.sub_type.clone()), star.system_address())
+        else {
+            return false;
+        };
+        let best = self.systems.entry(system.0).or_insert(boost);
+        *best = (*best).max(boost);
+        true
+    }
+
+    pub fn get(&self, system_id64: u64) -> Option<Boost> {
+        self.systems.get(&system_id64).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// Largest jump range multiplier in the map, 1 if it is empty.
+    pub fn max_factor(&self) -> f32 {
+        self.systems.values().max().map_or(1.0, |b| b.factor())
+    }
+}
+
+impl Extend<Body> for BoostMap {
+    fn extend<I: IntoIterator<Item = Body>>(&mut self, bodies: I) {
+        for body in bodies {
+            self.add(&body);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Fleet carrier routes.
+//!
+//! Tritium use follows the community-derived formula
+//! `round(5 + distance * (25000 + mass) / 200000)`, where `mass` is the carrier's used capacity
+//! plus the tritium in its tank.
+//!
+//! Routes do not stop to buy tritium, since the map does not say where it is sold, so they are
+//! limited to what the carrier carries.
+
+use anyhow::{bail, Context, Result};
+
+use super::{search, MapSystem, Optimize, StarMap, Waypoint};
+
+/// Longest fleet carrier jump in light years.
+pub const CARRIER_JUMP_RANGE: f32 = 500.0;
+
+/// Tritium tank capacity in tonnes.
+pub const CARRIER_TANK: u32 = 1000;
+
+/// Load of a fleet carrier, in tonnes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Carrier {
+    /// Used capacity of cargo, services and ship packs, excluding `reserve`.
+    pub used_capacity: u32,
+    /// Tritium in the tank.
+    pub fuel: u32,
+    /// Tritium in the cargo hold, moved to the tank when it runs low.
+    pub reserve: u32,
+}
+
+impl Carrier {
+    /// Mass that counts for jump costs.
+    pub fn mass(&self) -> u32 {
+        self.used_capacity + self.reserve + self.fuel
+    }
+
+    /// Tritium used by a jump of `distance` ly.
+    pub fn jump_cost(&self, distance: f32) -> u32 {
+        let mass = self.mass() as f64;
+        (5.0 + distance as f64 * (25000.0 + mass) / 200000.0).round() as u32
+    }
+
+    /// Jumps `distance` ly, refuelling from the reserve first if the tank is short.
+    ///
+    /// Returns the tritium used.
+    pub fn jump(&mut self, distance: f32) -> Result<u32> {
+        let cost = self.jump_cost(distance);
+        if self.fuel < cost {
+            let moved = self.reserve.min(CARRIER_TANK.saturating_sub(self.fuel));
+            self.reserve -= moved;
+            self.fuel += moved;
+        }
+        if self.fuel < cost {
+            bail!(
+                "not enough tritium for a {:.2} ly jump: needs {} t, has {} t",
+                distance,
+                cost,
+                self.fuel
+            );
+        }
+        self.fuel -= cost;
+        Ok(cost)
+    }
+}
+
+pub struct CarrierPlanner<'a> {
+    map: &'a StarMap,
+    carrier: Carrier,
+    jump_range: f32,
+    optimize: Optimize,
+}
+
+impl<'a> CarrierPlanner<'a> {
+    pub fn new(map: &'a StarMap, carrier: Carrier) -> CarrierPlanner<'a> {
+        CarrierPlanner {
+            map,
+            carrier,
+            jump_range: CARRIER_JUMP_RANGE,
+            optimize: Optimize::default(),
+        }
+    }
+
+    /// Limits jumps below [`CARRIER_JUMP_RANGE`].
+    pub fn jump_range(mut self, jump_range: f32) -> CarrierPlanner<'a> {
+        self.jump_range = jump_range.min(CARRIER_JUMP_RANGE);
+        self
+    }
+
+    pub fn optimize(mut self, optimize: Optimize) -> CarrierPlanner<'a> {
+        self.optimize = optimize;
+        self
+    }
+
+    /// Plans a route and the tritium it uses.
+    ///
+    /// Fails if the carrier runs out of tritium on the way: refuel stops are not planned, so a
+    /// longer trip has to be split at systems where tritium can be bought.
+    pub fn plan(&self, from: impl Into<Waypoint>, to: impl Into<Waypoint>) -> Result<CarrierRoute> {
+        let start = self.map.resolve(&from.into())?;
+        let goal = self.map.resolve(&to.into())?;
+        let range = self.jump_range;
+        let path = search(self.map, start, goal, range, self.optimize, |i, f| {
+            self.map.neighbors(i, range, f)
+        })?;
+
+        let mut carrier = self.carrier;
+        let mut hops: Vec<CarrierHop> = Vec::with_capacity(path.len());
+        for &i in &path {
+            let system = self.map.system(i).clone();
+            let distance = hops
+                .last()
+                .map_or(0.0, |h| h.system.coords.dist(system.coords));
+            let tritium = if hops.is_empty() {
+                0
+            } else {
+                carrier
+                    .jump(distance)
+                    .with_context(|| format!("jumping to {}", system.name))?
+            };
+            hops.push(CarrierHop {
+                system,
+                distance,
+                tritium,
+                carrier,
+            });
+        }
+        Ok(CarrierRoute { hops })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarrierRoute {
+    /// Systems along the route, starting with the start system.
+    pub hops: Vec<CarrierHop>,
+}
+
+impl CarrierRoute {
+    pub fn jumps(&self) -> usize {
+        self.hops.len().saturating_sub(1)
+    }
+
+    pub fn total_distance(&self) -> f32 {
+        self.hops.iter().map(|h| h.distance).sum()
+    }
+
+    pub fn total_tritium(&self) -> u32 {
+        self.hops.iter().map(|h| h.tritium).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarrierHop {
+    pub system: MapSystem,
+    /// Distance from the previous system, 0 for the start.
+    pub distance: f32,
+    /// Tritium used by the jump to this system, 0 for the start.
+    pub tritium: u32,
+    /// Load after arriving.
+    pub carrier: Carrier,
+}
@@ -0,0 +1,221 @@
+//! Schema drift report over a whole dump.
+//!
+//! The raw JSON of each entry is walked against the models, so every unknown field and enum value
+//! is found, whether or not the entry parses.
+//! Entries that cannot be parsed even [leniently](crate::model::lenient) are counted separately.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+
+use anyhow::Result;
+
+use crate::model::RootEntry;
+use crate::reader::{Position, RawEntries, RawEntry};
+
+/// Scans a whole dump of `T` entries and reports what the models do not know.
+///
+/// Only I/O errors and a malformed array are returned as `Err`;
+/// entries that cannot be parsed even leniently are counted in [`SchemaReport::failures`].
+pub fn schema_report<T: RootEntry, R: Read>(r: R) -> Result<SchemaReport> {
+    let mut report = SchemaReport::new(T::type_name());
+    for raw in RawEntries::new(r) {
+        report.add::<T>(&raw?);
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaReport {
+    /// [`RootEntry::type_name`] of the scanned entries.
+    pub entry_type: &'static str,
+    pub entries: u64,
+    /// Number of entries with at least one unknown field or enum value.
+    pub drifted_entries: u64,
+    /// Unknown fields by JSON path, with array indices elided (e.g. `bodies[].foo`).
+    pub unknown_fields: BTreeMap<String, Occurrence>,
+    /// Unknown enum values by enum type, then by value.
+    pub unknown_variants: BTreeMap<String, BTreeMap<String, Occurrence>>,
+    pub failures: Option<Failures>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    pub count: u64,
+    pub first: Example,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub position: Position,
+    pub line: String,
+}
+
+impl Example {
+    fn new(raw: &RawEntry) -> Example {
+        Example {
+            position: raw.position,
+            line: String::from_utf8_lossy(&raw.bytes).into_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failures {
+    pub count: u64,
+    pub first: Example,
+    pub first_error: String,
+}
+
+impl SchemaReport {
+    pub fn new(entry_type: &'static str) -> SchemaReport {
+        SchemaReport {
+            entry_type,
+            entries: 0,
+            drifted_entries: 0,
+            unknown_fields: BTreeMap::new(),
+            unknown_variants: BTreeMap::new(),
+            failures: None,
+        }
+    }
+
+    /// Returns `true` if every entry parsed strictly.
+    pub fn is_clean(&self) -> bool {
+        self.drifted_entries == 0 && self.failures.is_none()
+    }
+
+    pub fn add<T: RootEntry>(&mut self, raw: &RawEntry) {
+        self.entries += 1;
+
+        let scanned = T::scan_dump_json(&raw.bytes);
+
+        if let Err(e) = &scanned.value {
+            match &mut self.failures {
+                Some(f) => f.count += 1,
+                None => {
+                    self.failures = Some(Failures {
+                        count: 1,
+                        first: Example::new(raw),
+                        first_error: format!("{:#}", e),
+                    })
+                }
+            }
+        }
+
+        if scanned.is_exact() {
+            return;
+        }
+        self.drifted_entries += 1;
+
+        for path in scanned.unknown_fields.keys() {
+            count(&mut self.unknown_fields, elide_indices(path), raw);
+        }
+        for v in scanned.unknown_variants {
+            let values = self.unknown_variants.entry(v.enum_type.to_owned());
+            count(values.or_default(), v.value, raw);
+        }
+    }
+
+    /// Merges a report of another part of the same dump.
+    ///
+    /// Examples are taken from whichever report saw them first.
+    pub fn merge(&mut self, other: SchemaReport) {
+        self.entries += other.entries;
+        self.drifted_entries += other.drifted_entries;
+
+        merge_occurrences(&mut self.unknown_fields, other.unknown_fields);
+        for (k, values) in other.unknown_variants {
+            merge_occurrences(self.unknown_variants.entry(k).or_default(), values);
+        }
+
+        self.failures = match (self.failures.take(), other.failures) {
+            (None, f) | (f, None) => f,
+            (Some(mut a), Some(b)) => {
+                let count = a.count + b.count;
+                if b.first.position < a.first.position {
+                    a = b;
+                }
+                a.count = count;
+                Some(a)
+            }
+        };
+    }
+}
+
+fn count(map: &mut BTreeMap<String, Occurrence>, key: String, raw: &RawEntry) {
+    map.entry(key)
+        .and_modify(|o| o.count += 1)
+        .or_insert_with(|| Occurrence {
+            count: 1,
+            first: Example::new(raw),
+        });
+}
+
+fn merge_occurrences(to: &mut BTreeMap<String, Occurrence>, from: BTreeMap<String, Occurrence>) {
+    for (k, o) in from {
+        match to.get_mut(&k) {
+            Some(t) => {
+                t.count += o.count;
+                if o.first.position < t.first.position {
+                    t.first = o.first;
+                }
+            }
+            None => {
+                to.insert(k, o);
+            }
+        }
+    }
+}
+
+fn elide_indices(path: &str) -> String {
+    let mut s = String::with_capacity(path.len());
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => {
+                in_index = true;
+                s.push_str("[]");
+            }
+            ']' if in_index => in_index = false,
+            _ if in_index => {}
+            c => s.push(c),
+        }
+    }
+    s
+}
+
+impl fmt::Display for SchemaReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {} entries, {} drifted",
+            self.entry_type, self.entries, self.drifted_entries
+        )?;
+
+        for (path, o) in &self.unknown_fields {
+            writeln!(
+                f,
+                "  unknown field {} ({} times, first {})",
+                path, o.count, o.first.position
+            )?;
+        }
+        for (typ, values) in &self.unknown_variants {
+            for (value, o) in values {
+                writeln!(
+                    f,
+                    "  unknown {} `{}` ({} times, first {})",
+                    typ, value, o.count, o.first.position
+                )?;
+            }
+        }
+        if let Some(fail) = &self.failures {
+            writeln!(
+                f,
+                "  {} entries failed to parse, first {}: {}",
+                fail.count, fail.first.position, fail.first_error
+            )?;
+        }
+
+        Ok(())
+    }
+}
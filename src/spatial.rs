@@ -0,0 +1,338 @@
+//! Spatial index over system coordinates.
+//!
+//! [`SpatialIndex`] is a static k-d tree stored as a flat array: each range of the array is split at
+//! its middle element along the x, y and z axes in turn.
+//! The layout needs no pointers, so the index is written to disk as the array itself.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::iter::FromIterator;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::model::system::{Coords, HasCoords};
+use crate::model::RootEntry;
+
+const MAGIC: &[u8; 8] = b"EDSMKDT1";
+const POINT_BYTES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub id: u64,
+    pub coords: Coords,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbor {
+    pub id: u64,
+    pub coords: Coords,
+    pub dist: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpatialIndex {
+    points: Vec<Point>,
+}
+
+impl SpatialIndex {
+    pub fn new(mut points: Vec<Point>) -> SpatialIndex {
+        build(&mut points, 0);
+        SpatialIndex { points }
+    }
+
+    /// Builds an index of entries, keyed by [`RootEntry::entry_id`].
+    pub fn from_entries<T, I>(entries: I) -> SpatialIndex
+    where
+        T: RootEntry + HasCoords,
+        I: IntoIterator<Item = T>,
+    {
+        entries
+            .into_iter()
+            .map(|e| (e.entry_id(), e.coords()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Points in storage order.
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// Returns up to `k` points nearest to `center`, nearest first.
+    pub fn nearest(&self, center: Coords, k: usize) -> Vec<Neighbor> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.nearest_in(0, self.points.len(), 0, center, k, &mut heap);
+        }
+        let mut found: Vec<_> = heap
+            .into_iter()
+            .map(|c| self.neighbor(c.index, c.dist2))
+            .collect();
+        sort_by_dist(&mut found);
+        found
+    }
+
+    /// Returns points within `radius` of `center`, nearest first.
+    pub fn within_radius(&self, center: Coords, radius: f32) -> Vec<Neighbor> {
+        let mut found = Vec::new();
+        self.for_each_within(center, radius, |n| found.push(n));
+        sort_by_dist(&mut found);
+        found
+    }
+
+    /// Calls `f` with each point within `radius` of `center`, in no particular order.
+    pub fn for_each_within<F: FnMut(Neighbor)>(&self, center: Coords, radius: f32, mut f: F) {
+        let radius2 = radius * radius;
+        self.visit(
+            0,
+            self.points.len(),
+            0,
+            &mut |axis, p| {
+                let d = axis_value(center, axis) - axis_value(p.coords, axis);
+                let dist2 = p.coords.dist2(center);
+                let here = (dist2 <= radius2).then(|| Neighbor {
+                    id: p.id,
+                    coords: p.coords,
+                    dist: dist2.sqrt(),
+                });
+                (here, d <= radius, d >= -radius)
+            },
+            &mut f,
+        );
+    }
+
+    /// Returns points in the box between `min` and `max`, bounds included, in no particular order.
+    pub fn within_box(&self, min: Coords, max: Coords) -> Vec<Point> {
+        let mut found = Vec::new();
+        self.visit(
+            0,
+            self.points.len(),
+            0,
+            &mut |axis, p| {
+                let v = axis_value(p.coords, axis);
+                let c = p.coords;
+                let inside = (min.x..=max.x).contains(&c.x)
+                    && (min.y..=max.y).contains(&c.y)
+                    && (min.z..=max.z).contains(&c.z);
+                let here = inside.then_some(*p);
+                (here, axis_value(min, axis) <= v, v <= axis_value(max, axis))
+            },
+            &mut |p| found.push(p),
+        );
+        found
+    }
+
+    /// Writes the index in its binary format.
+    pub fn write_to<W: Write>(&self, mut w: W) -> Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&(self.points.len() as u64).to_le_bytes())?;
+        for p in &self.points {
+            w.write_all(&p.id.to_le_bytes())?;
+            for v in [p.coords.x, p.coords.y, p.coords.z] {
+                w.write_all(&v.to_le_bytes())?;
+            }
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Reads an index written by [`SpatialIndex::write_to`].
+    pub fn read_from<R: Read>(mut r: R) -> Result<SpatialIndex> {
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic).context("reading header")?;
+        if &magic != MAGIC {
+            bail!("not a spatial index file");
+        }
+        let mut len = [0; 8];
+        r.read_exact(&mut len).context("reading header")?;
+        let len = u64::from_le_bytes(len) as usize;
+
+        let mut points = Vec::with_capacity(len.min(1 << 24));
+        let mut buf = [0; POINT_BYTES];
+        for i in 0..len {
+            r.read_exact(&mut buf)
+                .with_context(|| format!("reading point {} of {}", i, len))?;
+            let f =
+                |at: usize| f32::from_le_bytes([buf[at], buf[at + 1], buf[at + 2], buf[at + 3]]);
+            let mut id = [0; 8];
+            id.copy_from_slice(&buf[..8]);
+            points.push(Point {
+                id: u64::from_le_bytes(id),
+                coords: Coords {
+                    x: f(8),
+                    y: f(12),
+                    z: f(16),
+                },
+            });
+        }
+        Ok(SpatialIndex { points })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let f = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+        self.write_to(BufWriter::new(f))
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<SpatialIndex> {
+        let path = path.as_ref();
+        let f = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        SpatialIndex::read_from(BufReader::new(f))
+            .with_context(|| format!("reading {}", path.display()))
+    }
+
+    fn neighbor(&self, index: usize, dist2: f32) -> Neighbor {
+        let p = self.points[index];
+        Neighbor {
+            id: p.id,
+            coords: p.coords,
+            dist: dist2.sqrt(),
+        }
+    }
+
+    fn nearest_in(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        center: Coords,
+        k: usize,
+        heap: &mut BinaryHeap<Candidate>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let p = &self.points[mid];
+        let axis = depth % 3;
+
+        let candidate = Candidate {
+            dist2: p.coords.dist2(center),
+            id: p.id,
+            index: mid,
+        };
+        if heap.len() < k {
+            heap.push(candidate);
+        } else if heap.peek().is_some_and(|c| candidate < *c) {
+            heap.pop();
+            heap.push(candidate);
+        }
+
+        let d = axis_value(center, axis) - axis_value(p.coords, axis);
+        let (near, far) = if d < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.nearest_in(near.0, near.1, depth + 1, center, k, heap);
+        if heap.len() < k || heap.peek().is_some_and(|c| d * d <= c.dist2) {
+            self.nearest_in(far.0, far.1, depth + 1, center, k, heap);
+        }
+    }
+
+    /// Walks the tree, passing values returned by `f` to `found`.
+    fn visit<T>(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        f: &mut Visitor<T>,
+        found: &mut dyn FnMut(T),
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (here, lower, upper) = f(depth % 3, &self.points[mid]);
+        if let Some(v) = here {
+            found(v);
+        }
+        if lower {
+            self.visit(lo, mid, depth + 1, f, found);
+        }
+        if upper {
+            self.visit(mid + 1, hi, depth + 1, f, found);
+        }
+    }
+}
+
+impl FromIterator<(u64, Coords)> for SpatialIndex {
+    fn from_iter<I: IntoIterator<Item = (u64, Coords)>>(iter: I) -> SpatialIndex {
+        SpatialIndex::new(
+            iter.into_iter()
+                .map(|(id, coords)| Point { id, coords })
+                .collect(),
+        )
+    }
+}
+
+/// Returns the value for a node on an axis, and whether to visit the lower and upper halves.
+type Visitor<'a, T> = dyn FnMut(usize, &Point) -> (Option<T>, bool, bool) + 'a;
+
+fn build(points: &mut [Point], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let mid = points.len() / 2;
+    let axis = depth % 3;
+    points.select_nth_unstable_by(mid, |a, b| {
+        axis_value(a.coords, axis).total_cmp(&axis_value(b.coords, axis))
+    });
+    let (lower, rest) = points.split_at_mut(mid);
+    build(lower, depth + 1);
+    build(&mut rest[1..], depth + 1);
+}
+
+fn axis_value(c: Coords, axis: usize) -> f32 {
+    match axis {
+        0 => c.x,
+        1 => c.y,
+        _ => c.z,
+    }
+}
+
+fn sort_by_dist(found: &mut [Neighbor]) {
+    found.sort_by(|a, b| a.dist.total_cmp(&b.dist).then(a.id.cmp(&b.id)));
+}
+
+/// Max-heap entry of the k-nearest search.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    dist2: f32,
+    id: u64,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        self.dist2
+            .total_cmp(&other.dist2)
+            .then(self.id.cmp(&other.id))
+            .then(self.index.cmp(&other.index))
+    }
+}
@@ -0,0 +1,252 @@
+//! Orbital hierarchy of a system.
+//!
+//! [`SystemTree`] links the bodies of one system through their [`Body::parents`](BodyT::parents)
+//! chains.
+//! Nodes are keyed by body ID, and barycentres (`Parent::Null`) become nodes without a body.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::model::body::{Body, BodyT, Parent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Star,
+    Planet,
+    Barycentre,
+    /// A [`Body::Unknown`] that no other body orbits, or a parent of unknown kind.
+    Unknown,
+}
+
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            NodeKind::Star => "star",
+            NodeKind::Planet => "planet",
+            NodeKind::Barycentre => "barycentre",
+            NodeKind::Unknown => "body",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub body_id: u64,
+    pub kind: NodeKind,
+    /// `None` for barycentres and for parents missing from the input.
+    pub body: Option<Body>,
+    pub parent: Option<u64>,
+    /// Body IDs of the children, in ascending order.
+    pub children: Vec<u64>,
+}
+
+impl Node {
+    fn new(body_id: u64, kind: NodeKind) -> Node {
+        Node {
+            body_id,
+            kind,
+            body: None,
+            parent: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn is_barycentre(&self) -> bool {
+        self.kind == NodeKind::Barycentre
+    }
+
+    /// Returns `true` if a body orbits this star or planet, but it is missing from the input.
+    pub fn is_dangling(&self) -> bool {
+        self.body.is_none() && !self.is_barycentre()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.body.as_ref().map(|b| b.name())
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.body {
+            Some(body) => write!(f, "{} ({} {})", body.name(), self.kind, self.body_id),
+            None if self.is_barycentre() => write!(f, "Barycentre {}", self.body_id),
+            None => write!(f, "Missing {} {}", self.kind, self.body_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemTree {
+    nodes: BTreeMap<u64, Node>,
+    unplaced: Vec<Body>,
+}
+
+impl SystemTree {
+    /// Builds the tree of the bodies of one system.
+    ///
+    /// If parent chains disagree, the first chain seen wins.
+    pub fn new<I: IntoIterator<Item = Body>>(bodies: I) -> SystemTree {
+        let mut tree = SystemTree::default();
+
+        for body in bodies {
+            let Some(body_id) = body.body_id() else {
+                tree.unplaced.push(body);
+                continue;
+            };
+
+            let kind = match &body {
+                Body::Star(_) => NodeKind::Star,
+                Body::Planet(_) => NodeKind::Planet,
+                Body::Unknown(_) => NodeKind::Unknown,
+            };
+            let chain: Vec<(u64, NodeKind)> = body
+                .parents()
+                .unwrap_or_default()
+                .iter()
+                .map(parent_node)
+                .collect();
+
+            let node = tree.node(body_id, kind);
+            if node.body.is_none() && kind != NodeKind::Unknown {
+                node.kind = kind;
+            }
+            node.body = Some(body);
+
+            let mut child = body_id;
+            for (id, kind) in chain {
+                tree.node(id, kind);
+                tree.link(child, id);
+                child = id;
+            }
+        }
+
+        for node in tree.nodes.values_mut() {
+            node.children.sort_unstable();
+            node.children.dedup();
+        }
+        tree
+    }
+
+    pub fn get(&self, body_id: u64) -> Option<&Node> {
+        self.nodes.get(&body_id)
+    }
+
+    /// Nodes in ascending order of body ID.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Nodes without a parent.
+    pub fn roots(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.values().filter(|n| n.parent.is_none())
+    }
+
+    pub fn parent(&self, body_id: u64) -> Option<&Node> {
+        self.nodes.get(&self.get(body_id)?.parent?)
+    }
+
+    pub fn children(&self, body_id: u64) -> impl Iterator<Item = &Node> {
+        self.get(body_id)
+            .into_iter()
+            .flat_map(|n| &n.children)
+            .filter_map(move |id| self.nodes.get(id))
+    }
+
+    /// Body IDs from the parent of `body_id` up to its root.
+    ///
+    /// Returns `None` for unknown IDs and for parent chains that loop.
+    pub fn ancestors(&self, body_id: u64) -> Option<Vec<u64>> {
+        let mut ancestors = Vec::new();
+        let mut cur = self.get(body_id)?;
+        while let Some(parent) = cur.parent {
+            if ancestors.len() >= self.nodes.len() {
+                return None;
+            }
+            ancestors.push(parent);
+            cur = self.get(parent)?;
+        }
+        Some(ancestors)
+    }
+
+    /// Number of ancestors of `body_id`, 0 for roots.
+    pub fn depth(&self, body_id: u64) -> Option<usize> {
+        self.ancestors(body_id).map(|a| a.len())
+    }
+
+    /// Stars and planets that bodies orbit, but that are missing from the input.
+    pub fn dangling(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.values().filter(|n| n.is_dangling())
+    }
+
+    /// Bodies without a body ID, which cannot be placed in the tree.
+    pub fn unplaced(&self) -> &[Body] {
+        &self.unplaced
+    }
+
+    fn node(&mut self, body_id: u64, kind: NodeKind) -> &mut Node {
+        self.nodes
+            .entry(body_id)
+            .or_insert_with(|| Node::new(body_id, kind))
+    }
+
+    fn link(&mut self, child: u64, parent: u64) {
+        if child == parent {
+            return;
+        }
+        let node = self.node(child, NodeKind::Unknown);
+        if node.parent.is_some() {
+            return;
+        }
+        node.parent = Some(parent);
+        self.node(parent, NodeKind::Barycentre).children.push(child);
+    }
+
+    fn render(&self, f: &mut fmt::Formatter, node: &Node, prefix: &str, last: bool) -> fmt::Result {
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        writeln!(f, "{}{}{}", prefix, branch, node)?;
+
+        let prefix = format!("{}{}", prefix, indent);
+        self.render_children(f, node, &prefix)
+    }
+
+    fn render_children(&self, f: &mut fmt::Formatter, node: &Node, prefix: &str) -> fmt::Result {
+        let children: Vec<_> = self.children(node.body_id).collect();
+        for (i, child) in children.iter().enumerate() {
+            self.render(f, child, prefix, i + 1 == children.len())?;
+        }
+        Ok(())
+    }
+}
+
+fn parent_node(parent: &Parent) -> (u64, NodeKind) {
+    match parent {
+        Parent::Null(id) => (*id, NodeKind::Barycentre),
+        Parent::Planet(id) => (*id, NodeKind::Planet),
+        Parent::Star(id) => (*id, NodeKind::Star),
+        Parent::Other(_, id) => (*id, NodeKind::Unknown),
+    }
+}
+
+/// Renders the tree with box-drawing characters, one root after another.
+impl fmt::Display for SystemTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for root in self.roots() {
+            writeln!(f, "{}", root)?;
+            self.render_children(f, root, "")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! Exploration data values, from the community-derived formulas for Odyssey.
+
+use crate::model::body::{
+    Body, Planet, PlanetSubType, Star, StarClass, StarSubType, TerraformingState,
+};
+
+const MASS_FACTOR: f64 = 0.56591828;
+const MIN_PLANET_VALUE: f64 = 500.0;
+const FIRST_DISCOVERY_MULTIPLIER: f64 = 2.6;
+
+/// How a body was scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Scan {
+    /// Mapped with the detailed surface scanner.
+    pub mapped: bool,
+    /// Mapped with no more probes than the efficiency target.
+    pub efficient: bool,
+    pub first_discovery: bool,
+    pub first_mapped: bool,
+}
+
+impl Scan {
+    /// Full spectrum scan only.
+    pub fn fss() -> Scan {
+        Scan::default()
+    }
+
+    /// Efficiently mapped.
+    pub fn mapped() -> Scan {
+        Scan {
+            mapped: true,
+            efficient: true,
+            ..Scan::default()
+        }
+    }
+
+    /// Adds first discovery, and first mapped if mapped.
+    pub fn first(self) -> Scan {
+        Scan {
+            first_discovery: true,
+            first_mapped: self.mapped,
+            ..self
+        }
+    }
+}
+
+pub fn body_value(body: &Body, scan: Scan) -> Option<u64> {
+    match body {
+        Body::Planet(p) => Some(planet_value(p, scan)),
+        Body::Star(s) => Some(star_value(s, scan)),
+        Body::Unknown(_) => None,
+    }
+}
+
+pub fn planet_value(planet: &Planet, scan: Scan) -> u64 {
+    let terraformable = matches!(
+        planet.terraforming_state,
+        Some(TerraformingState::CandidateForTerraforming | TerraformingState::Terraforming)
+    );
+    planet_value_of(
+        planet.sub_type.clone(),
+        terraformable,
+        planet.earth_masses,
+        scan,
+    )
+}
+
+/// Value of a planet of `sub_type` with the mass of `earth_masses`.
+///
+/// Earth-like worlds always get the terraformable bonus.
+pub fn planet_value_of(
+    sub_type: PlanetSubType,
+    terraformable: bool,
+    earth_masses: f32,
+    scan: Scan,
+) -> u64 {
+    let (base, terraform_bonus) = match sub_type {
+        PlanetSubType::MetalRichBody => (21790.0, 65631.0),
+        PlanetSubType::AmmoniaWorld => (96932.0, 0.0),
+        PlanetSubType::ClassIGasGiant => (1656.0, 0.0),
+        PlanetSubType::ClassIiGasGiant | PlanetSubType::HighMetalContentWorld => (9654.0, 100677.0),
+        PlanetSubType::WaterWorld => (64831.0, 116295.0),
+        PlanetSubType::EarthLikeWorld => (64831.0, 116295.0),
+        _ => (300.0, 93328.0),
+    };
+    let k = if terraformable || sub_type == PlanetSubType::EarthLikeWorld {
+        base + terraform_bonus
+    } else {
+        base
+    };
+
+    let mut value = k + k * MASS_FACTOR * (earth_masses as f64).powf(0.2);
+    if scan.mapped {
+        value *= match (scan.first_discovery, scan.first_mapped) {
+            (true, true) => 3.699622554,
+            (false, true) => 8.0956,
+            _ => 10.0 / 3.0,
+        };
+        value += (value * 0.3).max(555.0);
+        if scan.efficient {
+            value *= 1.25;
+        }
+    }
+    value = value.max(MIN_PLANET_VALUE);
+    if scan.first_discovery {
+        value *= FIRST_DISCOVERY_MULTIPLIER;
+    }
+    value.round() as u64
+}
+
+/// Value of a star. Stars cannot be mapped, so only first discovery counts.
+pub fn star_value(star: &Star, scan: Scan) -> u64 {
+    star_value_of(star.sub_type.clone(), star.solar_masses, scan)
+}
+
+pub fn star_value_of(sub_type: StarSubType, solar_masses: f32, scan: Scan) -> u64 {
+    let k = match sub_type {
+        StarSubType::NeutronStar | StarSubType::BlackHole => 22628.0,
+        StarSubType::SupermassiveBlackHole => 33.5678,
+        s if s.filter_star_class() == Some(StarClass::WhiteDwarfStars) => 14057.0,
+        _ => 1200.0,
+    };
+    let mut value = k + solar_masses as f64 * k / 66.25;
+    if scan.first_discovery {
+        value *= FIRST_DISCOVERY_MULTIPLIER;
+    }
+    value.round() as u64
+}
+
+/// Total value of the bodies of a system, each scanned as `scan`.
+pub fn system_value<'a, I: IntoIterator<Item = &'a Body>>(bodies: I, scan: Scan) -> u64 {
+    bodies.into_iter().filter_map(|b| body_value(b, scan)).sum()
+}
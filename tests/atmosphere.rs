@@ -0,0 +1,49 @@
+//! Tests for atmosphere descriptors.
+
+use strum::IntoEnumIterator;
+
+use edsm_dumps_model::model::body::{
+    AtmosphereBase, AtmosphereDensity, AtmosphereDescriptor, AtmosphereType,
+};
+
+#[test]
+fn atmosphere_type_descriptor() {
+    for t in AtmosphereType::iter() {
+        let d = t.descriptor().unwrap();
+        assert_eq!(AtmosphereType::from_descriptor(d), Some(t));
+    }
+    assert_eq!(
+        AtmosphereType::HotThickCarbonDioxideRich.descriptor(),
+        Some(AtmosphereDescriptor {
+            density: AtmosphereDensity::Thick,
+            hot: true,
+            rich: true,
+            base: AtmosphereBase::CarbonDioxide,
+        })
+    );
+    assert_eq!(
+        AtmosphereType::ThinSulphurDioxide.density(),
+        Some(AtmosphereDensity::Thin)
+    );
+    assert_eq!(
+        AtmosphereType::SuitableForWaterBasedLife.density(),
+        Some(AtmosphereDensity::Normal)
+    );
+    assert_eq!(
+        AtmosphereType::Other("Thin Xenon".to_owned()).density(),
+        None
+    );
+    let thin = AtmosphereType::iter()
+        .filter(|t| t.density() == Some(AtmosphereDensity::Thin))
+        .count();
+    assert_eq!(thin, 22);
+    assert_eq!(
+        AtmosphereType::from_descriptor(AtmosphereDescriptor {
+            density: AtmosphereDensity::Thin,
+            hot: true,
+            rich: false,
+            base: AtmosphereBase::Helium,
+        }),
+        None
+    );
+}
@@ -0,0 +1,118 @@
+//! Tests for transparent decompression of dump files.
+
+use std::fs;
+use std::io::{Cursor, Read};
+
+use anyhow::Result;
+
+use edsm_dumps_model::compression::{decompress, Compression};
+use edsm_dumps_model::model::system::SystemWithCoordinates;
+use edsm_dumps_model::reader::open_dump;
+
+const SAMPLE: &str = "./sampled_json/systemWithCoordinates.json";
+
+fn read_all(compressed: Vec<u8>) -> Result<Vec<u8>> {
+    let mut r = decompress(Cursor::new(compressed))?;
+    let mut buf = Vec::new();
+    r.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+#[test]
+fn detect_magic_bytes() {
+    assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+    assert_eq!(
+        Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]),
+        Compression::Zstd
+    );
+    assert_eq!(Compression::detect(b"BZh91AY"), Compression::Bzip2);
+    assert_eq!(
+        Compression::detect(&[0xfd, b'7', b'z', b'X', b'Z', 0x00, 0x00]),
+        Compression::Xz
+    );
+    assert_eq!(Compression::detect(b"[\n"), Compression::None);
+    assert_eq!(Compression::detect(b""), Compression::None);
+}
+
+#[test]
+fn open_uncompressed() -> Result<()> {
+    let systems = open_dump::<SystemWithCoordinates>(SAMPLE)?.collect::<Result<Vec<_>>>()?;
+    assert!(!systems.is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn open_gzip() -> Result<()> {
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let mut enc = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    enc.write_all(&fs::read(SAMPLE)?)?;
+    let compressed = enc.finish()?;
+
+    let path = temp_path("gz");
+    fs::write(&path, compressed)?;
+    let systems = open_dump::<SystemWithCoordinates>(&path)?.collect::<Result<Vec<_>>>();
+    fs::remove_file(&path)?;
+
+    assert!(!systems?.is_empty());
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "gzip"))]
+fn gzip_without_feature_is_error() {
+    let compressed = vec![0x1f, 0x8b, 0x08, 0x00];
+    let err = decompress(Cursor::new(compressed)).err().unwrap();
+    assert!(err.to_string().contains("`gzip` feature"));
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn decompress_zstd() -> Result<()> {
+    let original = fs::read(SAMPLE)?;
+    let compressed = zstd::encode_all(Cursor::new(&original), 0)?;
+    assert_eq!(read_all(compressed)?, original);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "bzip2")]
+fn decompress_bzip2() -> Result<()> {
+    use std::io::Write;
+
+    let original = fs::read(SAMPLE)?;
+    let mut enc = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+    enc.write_all(&original)?;
+    assert_eq!(read_all(enc.finish()?)?, original);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "xz")]
+fn decompress_xz() -> Result<()> {
+    use std::io::Write;
+
+    let original = fs::read(SAMPLE)?;
+    let mut enc = xz2::write::XzEncoder::new(Vec::new(), 6);
+    enc.write_all(&original)?;
+    assert_eq!(read_all(enc.finish()?)?, original);
+    Ok(())
+}
+
+#[test]
+fn decompress_uncompressed() -> Result<()> {
+    let original = fs::read(SAMPLE)?;
+    assert_eq!(read_all(original.clone())?, original);
+    Ok(())
+}
+
+#[cfg(feature = "gzip")]
+fn temp_path(ext: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "edsm-dumps-model-test-{}.json.{}",
+        std::process::id(),
+        ext
+    ))
+}
@@ -0,0 +1,148 @@
+//! Tests for the streaming dump readers.
+
+use std::fs::File;
+
+use anyhow::{Context, Result};
+
+use edsm_dumps_model::model::body::Body;
+use edsm_dumps_model::model::powerplay::PowerPlay;
+use edsm_dumps_model::model::system::SystemWithCoordinates;
+use edsm_dumps_model::reader::{DumpReader, Position, RawEntries};
+
+#[test]
+fn read_sampled_bodies() -> Result<()> {
+    let f = File::open("./sampled_json/body.json").context("failed to read file")?;
+    let bodies = DumpReader::<Body, _>::new(f).collect::<Result<Vec<_>>>()?;
+    assert!(!bodies.is_empty());
+    Ok(())
+}
+
+#[test]
+fn read_sampled_power_play() -> Result<()> {
+    let f = File::open("./sampled_json/powerPlay.json").context("failed to read file")?;
+    let entries = DumpReader::<PowerPlay, _>::new(f).collect::<Result<Vec<_>>>()?;
+    assert!(!entries.is_empty());
+    Ok(())
+}
+
+const SYSTEMS_LINES: &str = r#"[
+    {"coords":{"x":-198.0625,"y":276.03125,"z":0.1875},"date":"2015-05-12 15:29:33","id":4766,"id64":194580063412,"name":"HIP 73368"},
+    {"coords":{"x":87.25,"y":96.84375,"z":-65},"date":"2015-05-12 15:29:33","id":8713,"id64":663329196387,"name":"4 Sextantis"}
+]
+"#;
+
+const SYSTEMS_FORMATTED: &str = r#"[{"coords":{"x":-198.0625,"y":276.03125,"z":0.1875},
+  "date":"2015-05-12 15:29:33","id":4766,"id64":194580063412,"name":"HIP [73368]"},
+  {
+    "coords": {"x": 87.25, "y": 96.84375, "z": -65},
+    "date": "2015-05-12 15:29:33", "id": 8713, "id64": 663329196387, "name": "4 \"Sextantis\""
+  }]"#;
+
+const SYSTEMS_PRETTY: &str = r#"[
+  {
+    "coords": {
+      "x": -198.0625,
+      "y": 276.03125,
+      "z": 0.1875
+    },
+    "date": "2015-05-12 15:29:33",
+    "id": 4766,
+    "id64": 194580063412,
+    "name": "HIP 73368"
+  },
+  {
+    "coords": {
+      "x": 87.25,
+      "y": 96.84375,
+      "z": -65
+    },
+    "date": "2015-05-12 15:29:33",
+    "id": 8713,
+    "id64": 663329196387,
+    "name": "4 Sextantis"
+  }
+]
+"#;
+
+#[test]
+fn read_line_layout() -> Result<()> {
+    let systems = DumpReader::<SystemWithCoordinates, _>::new(SYSTEMS_LINES.as_bytes())
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(systems.len(), 2);
+    assert_eq!(systems[0].id, 4766);
+    assert_eq!(systems[1].id, 8713);
+    Ok(())
+}
+
+#[test]
+fn read_formatted_array() -> Result<()> {
+    let systems = DumpReader::<SystemWithCoordinates, _>::new(SYSTEMS_FORMATTED.as_bytes())
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(systems.len(), 2);
+    assert_eq!(systems[0].name, "HIP [73368]");
+    assert_eq!(systems[1].name, "4 \"Sextantis\"");
+    Ok(())
+}
+
+#[test]
+fn read_pretty_printed_array() -> Result<()> {
+    let systems = DumpReader::<SystemWithCoordinates, _>::new(SYSTEMS_PRETTY.as_bytes())
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(systems.len(), 2);
+    assert_eq!(systems[0].id, 4766);
+    assert_eq!(systems[1].name, "4 Sextantis");
+
+    let entries = RawEntries::new(SYSTEMS_PRETTY.as_bytes()).collect::<Result<Vec<_>>>()?;
+    assert_eq!(entries[0].position, Position { line: 2, offset: 4 });
+    assert_eq!(entries[1].position.line, 13);
+    Ok(())
+}
+
+#[test]
+fn read_line_layout_switching_to_fallback() -> Result<()> {
+    let first = SYSTEMS_LINES.lines().nth(1).unwrap();
+    let input = format!("[\n{}\n{}", first, &SYSTEMS_PRETTY[2..]);
+    let systems = DumpReader::<SystemWithCoordinates, _>::new(input.as_bytes())
+        .collect::<Result<Vec<_>>>()?;
+    let ids: Vec<_> = systems.iter().map(|s| s.id).collect();
+    assert_eq!(ids, [4766, 4766, 8713]);
+    Ok(())
+}
+
+#[test]
+fn raw_entry_positions() -> Result<()> {
+    let entries = RawEntries::new(SYSTEMS_FORMATTED.as_bytes()).collect::<Result<Vec<_>>>()?;
+    assert_eq!(entries[0].position, Position { line: 1, offset: 1 });
+    assert_eq!(entries[1].position.line, 3);
+    assert_eq!(
+        entries[1].position.offset as usize,
+        SYSTEMS_FORMATTED.find("{\n").unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn errors_carry_position() {
+    let input = SYSTEMS_LINES.replace("\"id\":8713", "\"id\":\"bad\"");
+    let results = DumpReader::<SystemWithCoordinates, _>::new(input.as_bytes()).collect::<Vec<_>>();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+
+    let err = results[1].as_ref().unwrap_err();
+    let pos = err
+        .downcast_ref::<Position>()
+        .expect("error should have position");
+    assert_eq!(pos.line, 3);
+    assert_eq!(
+        pos.offset as usize,
+        input.find("{\"coords\":{\"x\":87").unwrap()
+    );
+}
+
+#[test]
+fn truncated_dump_is_error() {
+    let input = SYSTEMS_LINES.trim_end().trim_end_matches(']');
+    let results = DumpReader::<SystemWithCoordinates, _>::new(input.as_bytes()).collect::<Vec<_>>();
+    assert_eq!(results.len(), 3);
+    assert!(results[2].is_err());
+}
@@ -0,0 +1,116 @@
+//! Tests for exobiology prediction.
+
+use std::fs::{self, File};
+
+use anyhow::Result;
+
+use edsm_dumps_model::exobiology::{self, RuleSet};
+use edsm_dumps_model::model::body::{AtmosphereType, Body, Planet, PlanetSubType, VolcanismType};
+use edsm_dumps_model::reader::DumpReader;
+
+fn planet() -> Result<Planet> {
+    let path = "./sampled_json/body.json";
+    for body in DumpReader::<Body, _>::new(File::open(path)?) {
+        if let Body::Planet(planet) = body? {
+            return Ok(Planet {
+                sub_type: PlanetSubType::RockyBody,
+                atmosphere_type: Some(AtmosphereType::ThinAmmonia),
+                volcanism_type: None,
+                is_landable: true,
+                gravity: Some(0.1),
+                surface_temperature: 170,
+                surface_pressure: Some(0.02),
+                ..planet
+            });
+        }
+    }
+    panic!("no planet in {}", path);
+}
+
+/// A partial table of 31 species with approximate conditions.
+fn rules() -> Result<RuleSet> {
+    RuleSet::from_json(&fs::read("./tests/fixtures/exobiology_rules.json")?)
+}
+
+fn species(rules: &RuleSet, planet: &Planet) -> Vec<String> {
+    rules
+        .predict(planet, None)
+        .iter()
+        .map(|p| p.species.to_owned())
+        .collect()
+}
+
+#[test]
+fn thin_ammonia_rocky_body() -> Result<()> {
+    let rules = &rules()?;
+    let planet = planet()?;
+
+    let predictions = rules.predict(&planet, None);
+    let found = species(rules, &planet);
+    assert_eq!(
+        found,
+        [
+            "Aleoida Laminiae",
+            "Aleoida Spica",
+            "Osseus Spiralis",
+            "Frutexa Flabellum",
+            "Fungoida Setisis",
+            "Bacterium Alcyoneum",
+            "Stratum Paleas",
+        ]
+    );
+    // Only one species of Aleoida can grow on the planet.
+    assert_eq!(
+        exobiology::max_value(&predictions),
+        predictions.iter().map(|p| p.value).sum::<u64>() - 3385200
+    );
+
+    let hot = Planet {
+        surface_temperature: 300,
+        ..planet.clone()
+    };
+    assert_eq!(
+        species(rules, &hot),
+        ["Aleoida Laminiae", "Aleoida Spica", "Stratum Paleas"]
+    );
+
+    let volcanic = Planet {
+        volcanism_type: Some(VolcanismType::MinorRockyMagma),
+        ..planet.clone()
+    };
+    assert!(species(rules, &volcanic).contains(&"Bacterium Tela".to_owned()));
+
+    let unlandable = Planet {
+        is_landable: false,
+        ..planet
+    };
+    assert!(rules.predict(&unlandable, None).is_empty());
+    Ok(())
+}
+
+#[test]
+fn custom_rules() -> Result<()> {
+    let json = br#"[
+        {
+            "genus": "Test",
+            "species": "Test Ammonia",
+            "value": 100,
+            "atmospheres": ["Thin Ammonia"],
+            "gravity": {"min": null, "max": 0.2}
+        },
+        {
+            "genus": "Test",
+            "species": "Test Star",
+            "value": 200,
+            "starClasses": ["BTypeStars"]
+        }
+    ]"#;
+    let rules = RuleSet::from_json(json)?;
+    assert_eq!(species(&rules, &planet()?), ["Test Ammonia"]);
+
+    assert!(RuleSet::from_json(
+        br#"[{"genus": "Test", "species": "Test", "value": 1, "colour": "red"}]"#
+    )
+    .is_err());
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! Tests for habitable zones.
+
+use std::fs::File;
+
+use anyhow::Result;
+
+use edsm_dumps_model::habitable::{self, SystemZones, Zone};
+use edsm_dumps_model::model::body::{Body, BodyT, Star, StarSubType};
+use edsm_dumps_model::reader::DumpReader;
+use edsm_dumps_model::system_tree::SystemTree;
+
+fn bodies() -> Result<Vec<Body>> {
+    DumpReader::<Body, _>::new(File::open("./sampled_json/body.json")?).collect()
+}
+
+fn sun() -> Result<Star> {
+    for body in bodies()? {
+        if let Body::Star(star) = body {
+            return Ok(Star {
+                sub_type: StarSubType::GWhiteYellowStar,
+                solar_radius: 1.0,
+                surface_temperature: 5778,
+                ..star
+            });
+        }
+    }
+    panic!("no star in sample");
+}
+
+#[test]
+fn sun_zones() -> Result<()> {
+    let zones = habitable::zones(&sun()?).unwrap();
+    let elw = zones.iter().find(|z| z.zone == Zone::EarthLike).unwrap();
+    // Earth at 499 ls is a 279 K black body, just closer than the 278 K inner edge.
+    assert!((elw.inner - 501.0).abs() < 1.0);
+    assert!(!elw.contains(499.0));
+    assert!(elw.contains(600.0));
+    let ammonia = zones.iter().find(|z| z.zone == Zone::AmmoniaWorld).unwrap();
+    assert!(ammonia.inner > elw.outer);
+    let icy = zones.iter().find(|z| z.zone == Zone::Icy).unwrap();
+    assert!(icy.outer.is_infinite());
+
+    let dead = Star {
+        surface_temperature: 0,
+        ..sun()?
+    };
+    assert!(habitable::zones(&dead).is_none());
+    Ok(())
+}
+
+#[test]
+fn binary_system_zones() -> Result<()> {
+    // Byooe Thaa QS-S c4-0
+    let tree = SystemTree::new(
+        bodies()?
+            .into_iter()
+            .filter(|b| b.system_id64() == Some(106065827114)),
+    );
+    let system = SystemZones::new(&tree);
+    let stars: Vec<_> = system.stars.iter().map(|s| s.body_id).collect();
+    assert_eq!(stars, [1, 2]);
+
+    let placed: Vec<_> = system.planets.iter().map(|p| (p.body_id, p.star)).collect();
+    assert_eq!(placed, [(3, 1), (4, 1), (7, 1), (16, 2), (17, 2)]);
+    for planet in &system.planets {
+        let star = system.star(planet.star).unwrap();
+        for zone in Zone::ALL {
+            assert_eq!(
+                planet.is_in(zone),
+                star.get(zone).unwrap().contains(planet.distance),
+                "{} {}",
+                planet.name,
+                zone
+            );
+        }
+    }
+    assert!(system
+        .planets_in(Zone::Habitable)
+        .all(|p| p.is_in(Zone::Habitable)));
+    Ok(())
+}
+
+#[test]
+fn binary_planet_zones() -> Result<()> {
+    // Hypheerld MD-W c17-5, whose A 7 orbits a barycentre around A.
+    let tree = SystemTree::new(
+        bodies()?
+            .into_iter()
+            .filter(|b| b.system_id64() == Some(1453476908954)),
+    );
+    let system = SystemZones::new(&tree);
+    let planet = system.planets.iter().find(|p| p.body_id == 12).unwrap();
+    assert_eq!(planet.name, "Hypheerld MD-W c17-5 A 7");
+    assert_eq!(planet.star, 1);
+    // Placed by distance to arrival, as the barycentre has no orbit.
+    assert_eq!(planet.distance, 3935.0);
+    assert!(planet.is_in(Zone::Icy));
+    assert!(system.unplaced.is_empty());
+    Ok(())
+}
+
+#[test]
+fn every_planet_is_placed_or_reported() -> Result<()> {
+    let bodies = bodies()?;
+    let mut systems: Vec<_> = bodies.iter().filter_map(|b| b.system_id64()).collect();
+    systems.sort_unstable();
+    systems.dedup();
+    for id64 in systems {
+        let in_system: Vec<_> = bodies
+            .iter()
+            .filter(|b| b.system_id64() == Some(id64))
+            .cloned()
+            .collect();
+        let system = SystemZones::new(&SystemTree::new(in_system.iter().cloned()));
+        for body in &in_system {
+            let (Body::Planet(_), Some(body_id)) = (body, body.body_id()) else {
+                continue;
+            };
+            let placed = system.planets.iter().any(|p| p.body_id == body_id);
+            let unplaced = system.unplaced.contains(&body_id);
+            assert!(placed != unplaced, "{}", body.name());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Tests for system addresses and procedural names.
+
+use std::fs::File;
+
+use anyhow::Result;
+
+use edsm_dumps_model::model::body::{Body, BodyT};
+use edsm_dumps_model::model::system::{
+    BoxelCoords, Coords, EstimatedCoords, Id64, MassCode, ProcGenName, SectorCoords,
+    SectorLocations, System, SystemWithCoordinates, SystemWithoutCoordinates,
+};
+use edsm_dumps_model::reader::DumpReader;
+
+#[test]
+fn sample_body_id64s_are_consistent() -> Result<()> {
+    for body in DumpReader::<Body, _>::new(File::open("./sampled_json/body.json")?) {
+        let body = body?;
+        body.validate_id64()?;
+
+        if let (Some(address), Some(body_id)) = (body.system_address(), body.body_id()) {
+            assert_eq!(address.with_body_id(body_id).map(u64::from), body.id64());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn sample_procgen_names_match_id64() -> Result<()> {
+    let path = "./sampled_json/systemWithCoordinates.json";
+    let mut sectors = SectorLocations::new();
+    for system in DumpReader::<SystemWithCoordinates, _>::new(File::open(path)?) {
+        let system = system?;
+        let (Some(name), Some(address)) = (system.procgen_name(), system.address()) else {
+            continue;
+        };
+        if name.sector.ends_with(" Sector") {
+            continue;
+        }
+        assert!(name.matches(address), "{}", system.name);
+        assert!(sectors.add(&system));
+        assert_eq!(sectors.id64(&name), Some(address));
+        let bounds = sectors.boxel_bounds(&name).unwrap();
+        assert!(bounds.contains(system.coords), "{}", system.name);
+    }
+    assert!(!sectors.is_empty());
+    Ok(())
+}
+
+#[test]
+fn sample_estimates_agree_with_id64() -> Result<()> {
+    let path = "./sampled_json/systemWithoutCoordinates.json";
+    for system in DumpReader::<SystemWithoutCoordinates, _>::new(File::open(path)?) {
+        let system = system?;
+        let (Some(estimate), Some(address)) = (&system.estimated_coordinates, system.address())
+        else {
+            continue;
+        };
+        assert!(
+            address.boxel_bounds().agrees_with(estimate),
+            "{}",
+            system.name
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn estimate_from_name_and_address() -> Result<()> {
+    let path = "./sampled_json/systemWithoutCoordinates.json";
+    let systems = DumpReader::<SystemWithoutCoordinates, _>::new(File::open(path)?)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut sectors = SectorLocations::new();
+    for system in &systems {
+        sectors.add(system);
+    }
+    assert!(!sectors.is_empty());
+
+    for system in systems {
+        let Some(edsm) = system.estimated_coordinates.clone() else {
+            continue;
+        };
+
+        let mut without_edsm = system.clone();
+        without_edsm.estimated_coordinates = None;
+        let from_address = without_edsm.estimate_coords(&sectors).unwrap();
+        assert_eq!(from_address, edsm, "{}", system.name);
+
+        if sectors
+            .get(&system.procgen_name().unwrap().sector)
+            .is_some()
+        {
+            without_edsm.id64 = None;
+            let from_name = without_edsm.estimate_coords(&sectors).unwrap();
+            assert_eq!(from_name, edsm, "{}", system.name);
+        }
+
+        let located = system.into_located(&sectors).unwrap();
+        assert!(located.is_estimated());
+        assert_eq!(located.coords, edsm.coords());
+    }
+    Ok(())
+}
+
+#[test]
+fn id64_decode_sol() {
+    let sol = Id64(10477373803);
+    assert_eq!(sol.mass_code(), MassCode::D);
+    assert_eq!(
+        sol.sector(),
+        SectorCoords {
+            x: 39,
+            y: 32,
+            z: 18
+        }
+    );
+    assert_eq!(sol.boxel(), BoxelCoords { x: 0, y: 0, z: 13 });
+    assert_eq!(sol.system_index(), 0);
+    assert!(
+        sol.boxel_center().dist(Coords {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0
+        }) < 40.0 * 3f32.sqrt()
+    );
+}
+
+#[test]
+fn id64_boxel_center() {
+    // Dryoi Pri JP-I c12-1459
+    let id64 = Id64(401129510708074);
+    assert_eq!(id64.mass_code(), MassCode::C);
+    assert_eq!(id64.system_index(), 1459);
+    assert_eq!(
+        id64.boxel_center(),
+        Coords {
+            x: -725.0,
+            y: -125.0,
+            z: 21235.0
+        }
+    );
+}
+
+#[test]
+fn id64_round_trip() {
+    for v in [
+        10477373803,
+        20578934,
+        401129510708074,
+        13876636756017,
+        3930935202970,
+    ] {
+        let id64 = Id64(v);
+        let rebuilt = Id64::from_parts(
+            id64.mass_code(),
+            id64.sector(),
+            id64.boxel(),
+            id64.system_index(),
+        );
+        assert_eq!(rebuilt, Some(id64));
+    }
+}
+
+#[test]
+fn mass_code_boxel_size() {
+    assert_eq!(MassCode::A.boxel_size(), 10.0);
+    assert_eq!(MassCode::H.boxel_size(), 1280.0);
+    assert_eq!(MassCode::from_letter('c'), Some(MassCode::C));
+    assert_eq!(MassCode::C.letter(), 'c');
+}
+
+#[test]
+fn procgen_name_round_trip() {
+    for name in [
+        "Pru Euq PC-D d12-36",
+        "Dryoi Pri JP-I c12-1459",
+        "Col 285 Sector EP-P a35-3",
+        "Hyades Sector AB-C d13",
+        "Hyades Sector AB-C d0-13",
+    ] {
+        assert_eq!(ProcGenName::parse(name).unwrap().to_string(), name);
+    }
+    let explicit = ProcGenName::parse("Hyades Sector AB-C d0-13").unwrap();
+    let implicit = ProcGenName::parse("Hyades Sector AB-C d13").unwrap();
+    assert_eq!(explicit.boxel(), implicit.boxel());
+    for name in [
+        "Sol",
+        "Col 285 Sector EP-P",
+        "HIP 12345",
+        "Foo AB-C i1-2",
+        "Foo ab-C d1",
+    ] {
+        assert_eq!(ProcGenName::parse(name), None);
+    }
+}
+
+#[test]
+fn procgen_name_matches_id64() {
+    let id64 = Id64(401129510708074);
+    let name = ProcGenName::parse("Dryoi Pri JP-I c12-1459").unwrap();
+    assert_eq!(name.mass_code, MassCode::C);
+    assert_eq!(name.n1, Some(12));
+    assert_eq!(name.system_index(), 1459);
+    assert_eq!(name.boxel(), Some(id64.boxel()));
+    assert!(name.matches(id64));
+    assert_eq!(
+        ProcGenName::new("Dryoi Pri", id64.mass_code(), id64.boxel(), 1459),
+        name
+    );
+}
+
+#[test]
+fn boxel_bounds_estimate() {
+    let bounds = Id64(401129510708074).boxel_bounds();
+    assert_eq!(
+        bounds.estimated_coords(),
+        EstimatedCoords {
+            x: -725.0,
+            y: -125.0,
+            z: 21235.0,
+            precision: 35.0,
+        }
+    );
+    assert!(bounds.contains(bounds.center()));
+    assert!(!bounds.contains(bounds.max() + bounds.max()));
+    assert_eq!(
+        SectorCoords::containing(bounds.center()),
+        Some(Id64(401129510708074).sector())
+    );
+}
+
+#[test]
+fn id64_body() {
+    let body = Id64(540433202695637483);
+    assert_eq!(body.system(), Id64(1247411177963));
+    assert_eq!(body.body_id(), 15);
+    assert_eq!(body.system().with_body_id(15), Some(body));
+    assert_eq!(body.with_body_id(0), Some(body.system()));
+    assert_eq!(body.with_body_id(512), None);
+    assert_eq!(body.boxel_center(), body.system().boxel_center());
+}
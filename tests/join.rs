@@ -0,0 +1,218 @@
+//! Tests for joining dumps by system address.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+
+use anyhow::Result;
+
+use edsm_dumps_model::join::{SystemJoin, SystemRecord, MAX_FAN_IN};
+use edsm_dumps_model::model::body::{Body, BodyT, PlanetSubType};
+use edsm_dumps_model::model::powerplay::PowerPlay;
+use edsm_dumps_model::model::station::Station;
+use edsm_dumps_model::model::system::{System, SystemWithCoordinates};
+use edsm_dumps_model::model::system_populated::SystemPopulated;
+use edsm_dumps_model::model::RootEntry;
+use edsm_dumps_model::reader::DumpReader;
+
+fn read<T: RootEntry>(name: &str) -> Result<Vec<T>> {
+    DumpReader::<T, _>::new(File::open(format!("./sampled_json/{}.json", name))?).collect()
+}
+
+fn ok<T>(entries: Vec<T>) -> impl Iterator<Item = Result<T>> {
+    entries.into_iter().map(Ok)
+}
+
+fn join(
+    systems: Vec<SystemWithCoordinates>,
+    bodies: Vec<Body>,
+    powerplay: Vec<PowerPlay>,
+) -> SystemJoin<'static> {
+    SystemJoin::new()
+        .systems(ok(systems))
+        .bodies(ok(bodies))
+        .powerplay(ok(powerplay))
+}
+
+#[test]
+fn sorted_and_spilled_agree() -> Result<()> {
+    let mut systems: Vec<SystemWithCoordinates> = read("systemWithCoordinates")?;
+    let mut bodies: Vec<Body> = read("body")?;
+    let mut powerplay: Vec<PowerPlay> = read("powerPlay")?;
+
+    let dir = std::env::temp_dir().join(format!("edsm-dumps-model-join-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let spilled = join(systems.clone(), bodies.clone(), powerplay.clone())
+        .spilled(&dir, 16)?
+        .collect::<Result<Vec<SystemRecord>>>()?;
+    assert_eq!(fs::read_dir(&dir)?.count(), 0);
+
+    // One run per entry, more than can be merged at once.
+    assert!(systems.len() + bodies.len() + powerplay.len() > 2 * MAX_FAN_IN);
+    let merged_in_passes = join(systems.clone(), bodies.clone(), powerplay.clone())
+        .spilled(&dir, 1)?
+        .collect::<Result<Vec<SystemRecord>>>()?;
+    assert_eq!(merged_in_passes, spilled);
+    assert_eq!(fs::read_dir(&dir)?.count(), 0);
+    fs::remove_dir(&dir)?;
+
+    systems.sort_by_key(|s| s.id64());
+    bodies.sort_by_key(|b| b.system_address().map(|a| a.0));
+    powerplay.sort_by_key(|p| p.id64);
+    let expected_bodies = bodies
+        .iter()
+        .filter(|b| b.system_address().is_some())
+        .count();
+    let sorted = join(systems, bodies, powerplay)
+        .sorted()
+        .collect::<Result<Vec<SystemRecord>>>()?;
+
+    assert!(sorted.windows(2).all(|w| w[0].id64 < w[1].id64));
+    assert_eq!(sorted.len(), spilled.len());
+    for (a, b) in sorted.iter().zip(&spilled) {
+        assert_eq!(a.id64, b.id64);
+        assert_eq!(a.system, b.system);
+        assert_eq!(a.powerplay, b.powerplay);
+        assert_eq!(a.bodies, b.bodies);
+    }
+    let joined_bodies: usize = sorted.iter().map(|r| r.bodies.len()).sum();
+    assert_eq!(joined_bodies, expected_bodies);
+
+    for record in &sorted {
+        for body in &record.bodies {
+            assert_eq!(body.system_address().map(|a| a.0), Some(record.id64));
+        }
+        assert!(record.name().is_some());
+    }
+    Ok(())
+}
+
+#[test]
+fn unsorted_input_fails() -> Result<()> {
+    let mut systems: Vec<SystemWithCoordinates> = read("systemWithCoordinates")?;
+    systems.retain(|s| s.id64.is_some());
+    systems.sort_by_key(|s| std::cmp::Reverse(s.id64));
+    let result = SystemJoin::new()
+        .systems(ok(systems))
+        .sorted()
+        .collect::<Result<Vec<_>>>();
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn one_record_per_system() -> Result<()> {
+    let bodies: Vec<Body> = read("body")?;
+    let mut counts = BTreeMap::new();
+    for b in &bodies {
+        if let Some(a) = b.system_address() {
+            *counts.entry(a.0).or_insert(0) += 1;
+        }
+    }
+    let dir = std::env::temp_dir();
+    let records = SystemJoin::new()
+        .bodies(ok(bodies))
+        .spilled(&dir, 1000)?
+        .map(|r| r.map(|r| (r.id64, r.bodies.len())))
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    assert_eq!(records, counts);
+    Ok(())
+}
+
+#[test]
+fn spilled_keeps_other_values() -> Result<()> {
+    let mut bodies: Vec<Body> = read("body")?;
+    let i = bodies
+        .iter()
+        .position(|b| matches!(b, Body::Planet(_)) && b.system_address().is_some())
+        .unwrap();
+    let Body::Planet(p) = &mut bodies[i] else {
+        unreachable!()
+    };
+    p.sub_type = PlanetSubType::Other("Crystal world".to_owned());
+    let expected = bodies[i].clone();
+
+    let dir = std::env::temp_dir().join(format!(
+        "edsm-dumps-model-join-other-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir)?;
+    let records = SystemJoin::new()
+        .bodies(ok(bodies))
+        .spilled(&dir, 4)?
+        .collect::<Result<Vec<SystemRecord>>>()?;
+    fs::remove_dir(&dir)?;
+
+    assert!(records.iter().any(|r| r.bodies.contains(&expected)));
+    Ok(())
+}
+
+#[test]
+fn spilled_round_trips_stations_and_populated() -> Result<()> {
+    let station: Station = serde_json::from_str(
+        r#"{"id":1,"haveMarket":true,"haveOutfitting":false,"haveShipyard":true,"name":"A Port",
+        "otherServices":["Refuel"],"ships":[{"id":128049249,"name":"Sidewinder"},{"id":2,"name":42}],
+        "systemId64":10477373803,"type":"Coriolis Starport",
+        "updateTime":{"information":"2024-01-01 00:00:00","market":"2024-01-02 03:04:05","shipyard":null}}"#,
+    )?;
+    let populated: SystemPopulated = serde_json::from_str(
+        r#"{"id":27,"bodies":[],"controllingFaction":{"id":1,"name":"Mother Gaia"},
+        "coords":{"x":0,"y":0,"z":0},"date":"2024-01-01 00:00:00",
+        "factions":[{"id":1,"activeStates":[{"state":"Boom"}],"influence":0.5,"isPlayer":false,
+        "lastUpdate":1704067200,"name":"Mother Gaia","pendingStates":[],"recoveringStates":[]}],
+        "id64":10477373803,"name":"Sol","security":"High",
+        "stations":[{"id":1,"haveMarket":true,"haveOutfitting":false,"haveShipyard":true,
+        "name":"A Port","otherServices":[],"updateTime":{"information":"2024-01-01 00:00:00"}}]}"#,
+    )?;
+
+    let dir = std::env::temp_dir().join(format!(
+        "edsm-dumps-model-join-round-trip-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir)?;
+    let records = SystemJoin::new()
+        .stations(ok(vec![station.clone()]))
+        .populated(ok(vec![populated.clone()]))
+        .spilled(&dir, 1)?
+        .collect::<Result<Vec<SystemRecord>>>()?;
+    fs::remove_dir(&dir)?;
+
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].stations, [station]);
+    assert_eq!(records[0].populated, Some(populated));
+    Ok(())
+}
+
+#[test]
+fn later_duplicates_replace_earlier() -> Result<()> {
+    let mut systems: Vec<SystemWithCoordinates> = read("systemWithCoordinates")?;
+    systems.retain(|s| s.id64.is_some());
+    let first = systems[0].clone();
+    let mut second = first.clone();
+    second.name = "Renamed".to_owned();
+    let mut third = first.clone();
+    third.name = "Renamed again".to_owned();
+
+    let dir = std::env::temp_dir().join(format!(
+        "edsm-dumps-model-join-duplicates-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir)?;
+    let spilled = SystemJoin::new()
+        .systems(ok(vec![first.clone(), second.clone()]))
+        .systems(ok(vec![third.clone()]))
+        .spilled(&dir, 1)?
+        .collect::<Result<Vec<SystemRecord>>>()?;
+    fs::remove_dir(&dir)?;
+    let sorted = SystemJoin::new()
+        .systems(ok(vec![first, second]))
+        .systems(ok(vec![third.clone()]))
+        .sorted()
+        .collect::<Result<Vec<SystemRecord>>>()?;
+
+    for records in [spilled, sorted] {
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].system.as_ref(), Some(&third));
+        assert_eq!(records[0].replaced, 2);
+    }
+    Ok(())
+}
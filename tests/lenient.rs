@@ -0,0 +1,204 @@
+//! Tests for lenient parsing.
+
+use std::fs;
+
+use anyhow::Result;
+use serde_json::{json, to_vec, Value};
+
+use edsm_dumps_model::model::body::{AtmosphereType, Body, MaterialsKey, Parent, PlanetSubType};
+use edsm_dumps_model::model::station::{OtherService, Station};
+use edsm_dumps_model::model::RootEntry;
+
+fn sample_planet() -> Value {
+    let s = fs::read_to_string("./sampled_json/body.json").unwrap();
+    let line = s.lines().nth(1).unwrap().trim().trim_end_matches(',');
+    serde_json::from_str(line).unwrap()
+}
+
+fn planet(body: Body) -> edsm_dumps_model::model::body::Planet {
+    match body {
+        Body::Planet(p) => p,
+        b => panic!("expected planet: {:?}", b),
+    }
+}
+
+#[test]
+fn exact_entry() -> Result<()> {
+    let parsed = Body::parse_dump_json_lenient(&to_vec(&sample_planet())?)?;
+    assert!(parsed.is_exact());
+    assert_eq!(
+        parsed.value,
+        Body::parse_dump_json(&to_vec(&sample_planet())?)?
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_fields_are_collected() -> Result<()> {
+    let mut v = sample_planet();
+    v["fooBar"] = json!(1);
+    v["solidComposition"]["Gas"] = json!(2.5);
+    let bs = to_vec(&v)?;
+    // Strict parsing falls back to `Body::Unknown`.
+    assert!(matches!(Body::parse_dump_json(&bs)?, Body::Unknown(_)));
+
+    let parsed = Body::parse_dump_json_lenient(&bs)?;
+    assert_eq!(parsed.unknown_fields.get("fooBar"), Some(&json!(1)));
+    assert_eq!(
+        parsed.unknown_fields.get("solidComposition.Gas"),
+        Some(&json!(2.5))
+    );
+    assert!(parsed.unknown_variants.is_empty());
+    assert_eq!(planet(parsed.value).radius, 5382.9335);
+    Ok(())
+}
+
+#[test]
+fn unknown_variants_are_reported() -> Result<()> {
+    let mut v = sample_planet();
+    v["atmosphereType"] = json!("Thin Xenon");
+    v["materials"]["Unobtanium"] = json!(1.0);
+    v["parents"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({"Moon": 7}));
+
+    let parsed = Body::parse_dump_json_lenient(&to_vec(&v)?)?;
+    let mut found: Vec<_> = parsed
+        .unknown_variants
+        .iter()
+        .map(|u| (u.path.as_str(), u.enum_type, u.value.as_str()))
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        vec![
+            ("atmosphereType", "AtmosphereType", "Thin Xenon"),
+            ("materials.Unobtanium", "MaterialsKey", "Unobtanium"),
+            ("parents[3]", "Parent", "Moon"),
+        ]
+    );
+
+    let p = planet(parsed.value);
+    assert_eq!(
+        p.atmosphere_type,
+        Some(AtmosphereType::Other("Thin Xenon".to_owned()))
+    );
+    let materials = p.materials.unwrap();
+    assert_eq!(materials.get(MaterialsKey::Iron), Some(21.76));
+    assert_eq!(
+        materials.get(MaterialsKey::Other("Unobtanium".to_owned())),
+        None
+    );
+    assert_eq!(
+        p.parents.unwrap().last(),
+        Some(&Parent::Other("Moon".to_owned(), 7))
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_variants_serialize_as_read() -> Result<()> {
+    let mut v = sample_planet();
+    v["atmosphereType"] = json!("Thin Xenon");
+    v["parents"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({"Moon": 7}));
+
+    let parsed = Body::parse_dump_json_lenient(&to_vec(&v)?)?;
+    let written = serde_json::to_value(&parsed.value)?;
+    assert_eq!(written["atmosphereType"], json!("Thin Xenon"));
+    assert_eq!(written["parents"][3], json!({"Moon": 7}));
+    let reread = Body::parse_dump_json_lenient(&to_vec(&written)?)?;
+    assert_eq!(reread.value, parsed.value);
+    assert_eq!(
+        AtmosphereType::Other("Thin Xenon".to_owned()).to_string(),
+        "Thin Xenon"
+    );
+    Ok(())
+}
+
+#[test]
+fn strict_parsing_rejects_other() {
+    let e = serde_json::from_str::<AtmosphereType>(r#""Thin Xenon""#).unwrap_err();
+    assert!(!e.to_string().contains("`Other`"), "{}", e);
+    assert!(serde_json::from_str::<AtmosphereType>(r#"{"Other":"Thin Xenon"}"#).is_err());
+    assert!(serde_json::from_str::<AtmosphereType>(r#""Other""#).is_err());
+    assert!(serde_json::from_str::<Parent>(r#"{"Moon":7}"#).is_err());
+    assert!(serde_json::from_str::<Parent>(r#"{"Other":["Moon",7]}"#).is_err());
+}
+
+#[test]
+fn unknown_array_element_is_kept() -> Result<()> {
+    let v = json!({
+        "id": 1,
+        "haveMarket": false,
+        "haveOutfitting": false,
+        "haveShipyard": false,
+        "name": "Test Station",
+        "otherServices": ["Refuel", "Holo-Me", "Repair"],
+        "updateTime": {"information": "2024-01-01 00:00:00"}
+    });
+    let bs = to_vec(&v)?;
+    assert!(Station::parse_dump_json(&bs).is_err());
+
+    let parsed = Station::parse_dump_json_lenient(&bs)?;
+    assert_eq!(parsed.unknown_variants.len(), 1);
+    assert_eq!(parsed.unknown_variants[0].path, "otherServices[1]");
+    assert_eq!(parsed.unknown_variants[0].enum_type, "OtherService");
+    assert_eq!(
+        parsed.value.other_services,
+        vec![
+            OtherService::Refuel,
+            OtherService::Other("Holo-Me".to_owned()),
+            OtherService::Repair
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_required_variant_keeps_typed_data() -> Result<()> {
+    let mut v = sample_planet();
+    v["subType"] = json!("Crystal world");
+    v["fooBar"] = json!(1);
+    let parsed = Body::parse_dump_json_lenient(&to_vec(&v)?)?;
+    assert_eq!(parsed.unknown_variants.len(), 1);
+    assert!(parsed.unknown_fields.contains_key("fooBar"));
+    let p = planet(parsed.value);
+    assert_eq!(p.sub_type, PlanetSubType::Other("Crystal world".to_owned()));
+    assert_eq!(p.radius, 5382.9335);
+    Ok(())
+}
+
+#[test]
+fn unknown_body_type_falls_back() -> Result<()> {
+    let mut v = sample_planet();
+    v["type"] = json!("Comet");
+    let parsed = Body::parse_dump_json_lenient(&to_vec(&v)?)?;
+    assert!(matches!(parsed.value, Body::Unknown(_)));
+    assert_eq!(parsed.unknown_variants[0].enum_type, "Body");
+    assert_eq!(parsed.unknown_variants[0].value, "Comet");
+    Ok(())
+}
+
+#[test]
+fn scan_keeps_findings_of_failed_entry() -> Result<()> {
+    let v = json!({
+        "id": 1,
+        "haveMarket": "yes",
+        "haveOutfitting": false,
+        "haveShipyard": false,
+        "name": "Test Station",
+        "otherServices": ["Holo-Me"],
+        "updateTime": {"information": "2024-01-01 00:00:00"},
+        "extra": 1
+    });
+    let scanned = Station::scan_dump_json(&to_vec(&v)?);
+    assert!(scanned.value.is_err());
+    assert!(scanned.unknown_fields.contains_key("extra"));
+    assert_eq!(scanned.unknown_variants[0].value, "Holo-Me");
+    assert!(scanned.transpose().is_err());
+    Ok(())
+}
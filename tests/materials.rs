@@ -0,0 +1,127 @@
+//! Tests for the material finder.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+
+use edsm_dumps_model::materials::{Jumponium, Match, MaterialFinder, SortBy};
+use edsm_dumps_model::model::body::{Body, BodyT, MaterialGrade, MaterialsKey};
+use edsm_dumps_model::model::system::{Coords, SystemWithCoordinates};
+use edsm_dumps_model::reader::DumpReader;
+
+fn bodies() -> Result<Vec<Body>> {
+    DumpReader::<Body, _>::new(File::open("./sampled_json/body.json")?).collect()
+}
+
+/// One system per system address of the sample bodies, 10 ly apart along x.
+fn systems(bodies: &[Body]) -> Vec<SystemWithCoordinates> {
+    let id64s: BTreeSet<u64> = bodies.iter().filter_map(|b| b.system_id64()).collect();
+    id64s
+        .into_iter()
+        .enumerate()
+        .map(|(i, id64)| SystemWithCoordinates {
+            id: i as u64,
+            coords: Coords {
+                x: 10.0 * i as f32,
+                y: 0.0,
+                z: 0.0,
+            },
+            id64: Some(id64),
+            name: format!("System {}", i),
+            date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        })
+        .collect()
+}
+
+#[test]
+fn find_and_place() -> Result<()> {
+    let bodies = bodies()?;
+    let origin = Coords {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let mut finder = MaterialFinder::new([MaterialsKey::Arsenic, MaterialsKey::Carbon], origin);
+
+    let kept = bodies.iter().filter(|b| finder.add_body(b)).count();
+    assert!(kept > 0);
+    assert_eq!(finder.unplaced(), kept);
+    assert!(finder.deposits().is_empty());
+
+    let placed: usize = systems(&bodies).iter().map(|s| finder.add_system(s)).sum();
+    assert_eq!(placed, kept);
+    assert_eq!(finder.unplaced(), 0);
+
+    let deposits = finder.deposits();
+    for d in deposits {
+        let Some(Body::Planet(planet)) = bodies.iter().find(|b| b.id() == d.body_id) else {
+            panic!("no planet {}", d.body_id);
+        };
+        assert!(planet.is_landable);
+        assert_eq!(d.name, planet.name);
+        let materials = planet.materials.as_ref().unwrap();
+        let arsenic = materials.get(MaterialsKey::Arsenic).unwrap();
+        let carbon = materials.get(MaterialsKey::Carbon).unwrap();
+        assert_eq!(d.percent, arsenic.min(carbon));
+        assert_eq!(d.distance, d.coords.x);
+    }
+    assert!(deposits.windows(2).all(|w| w[0].percent >= w[1].percent));
+
+    let by_distance = finder.sorted(SortBy::Distance);
+    assert!(by_distance
+        .windows(2)
+        .all(|w| w[0].distance <= w[1].distance));
+    Ok(())
+}
+
+#[test]
+fn filters() -> Result<()> {
+    let bodies = bodies()?;
+    let systems = systems(&bodies);
+    let origin = Coords {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let run = |finder: MaterialFinder| {
+        let mut finder = finder;
+        for b in &bodies {
+            finder.add_body(b);
+        }
+        for s in &systems {
+            finder.add_system(s);
+        }
+        finder.into_deposits(SortBy::Percent)
+    };
+
+    let all = run(MaterialFinder::new(Jumponium::Basic.materials(), origin));
+    let any = run(MaterialFinder::new(Jumponium::Basic.materials(), origin).matching(Match::Any));
+    assert!(any.len() >= all.len());
+    assert!(!any.is_empty());
+
+    let rich = run(MaterialFinder::new([MaterialsKey::Iron], origin).min_percent(20.0));
+    assert!(rich.iter().all(|d| d.percent >= 20.0));
+    let near = run(MaterialFinder::new([MaterialsKey::Iron], origin).max_distance(15.0));
+    assert!(near.iter().all(|d| d.distance <= 15.0));
+    assert!(near.len() < run(MaterialFinder::new([MaterialsKey::Iron], origin)).len());
+    Ok(())
+}
+
+#[test]
+fn grades() {
+    assert_eq!(MaterialsKey::Iron.grade(), Some(MaterialGrade::VeryCommon));
+    assert_eq!(MaterialsKey::Germanium.grade(), Some(MaterialGrade::Common));
+    assert_eq!(MaterialsKey::Niobium.grade(), Some(MaterialGrade::Standard));
+    assert_eq!(
+        MaterialsKey::Polonium.grade().map(MaterialGrade::number),
+        Some(4)
+    );
+    assert_eq!(MaterialsKey::Other("Unobtanium".to_owned()).grade(), None);
+    let premium = Jumponium::Premium.materials();
+    assert_eq!(
+        premium.iter().filter_map(|m| m.grade()).max(),
+        Some(MaterialGrade::Rare)
+    );
+}
@@ -0,0 +1,50 @@
+//! Tests for Keplerian orbits.
+
+use chrono::{Duration, TimeZone, Utc};
+
+use edsm_dumps_model::orbit::Orbit;
+
+fn orbit(eccentricity: f64) -> Orbit {
+    Orbit {
+        semi_major_axis: 2.0,
+        eccentricity,
+        inclination: 0.0,
+        arg_of_periapsis: 0.0,
+        ascending_node: 0.0,
+        period: 100.0,
+        mean_anomaly_at_epoch: 0.0,
+        epoch: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn circular_orbit() {
+    let o = orbit(0.0);
+    let quarter = o.position_at(o.epoch + Duration::days(25));
+    assert!(close(quarter.x, 0.0) && close(quarter.y, 2.0) && close(quarter.z, 0.0));
+    assert!(close(o.distance_at(o.epoch + Duration::days(1234)), 2.0));
+}
+
+#[test]
+fn eccentric_orbit() {
+    let o = orbit(0.5);
+    assert!(close(o.periapsis(), 1.0));
+    assert!(close(o.apoapsis(), 3.0));
+    assert!(close(o.distance_at(o.epoch), 1.0));
+    assert!(close(o.distance_at(o.epoch + Duration::days(50)), 3.0));
+    assert!(close(o.distance_at(o.epoch - Duration::days(150)), 3.0));
+}
+
+#[test]
+fn inclined_orbit() {
+    let o = Orbit {
+        inclination: 90.0,
+        ..orbit(0.0)
+    };
+    let quarter = o.position_at(o.epoch + Duration::days(25));
+    assert!(close(quarter.y, 0.0) && close(quarter.z, 2.0));
+}
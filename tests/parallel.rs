@@ -0,0 +1,105 @@
+//! Tests for parallel dump parsing.
+
+#![cfg(feature = "parallel")]
+
+use std::fs::File;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Result;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+
+use edsm_dumps_model::model::body::{Body, BodyT};
+use edsm_dumps_model::parallel::ParallelReader;
+use edsm_dumps_model::reader::{DumpReader, Position};
+
+const SAMPLE: &str = "./sampled_json/body.json";
+
+fn sequential_ids() -> Result<Vec<u64>> {
+    DumpReader::<Body, _>::new(File::open(SAMPLE)?)
+        .map(|b| b.map(|b| b.id()))
+        .collect()
+}
+
+#[test]
+fn ordered_matches_sequential() -> Result<()> {
+    let ids = ParallelReader::new(File::open(SAMPLE)?)
+        .batch_size(7)
+        .ordered::<Body>()
+        .map(|b| b.map(|b| b.id()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(ids, sequential_ids()?);
+    Ok(())
+}
+
+#[test]
+fn unordered_yields_all() -> Result<()> {
+    let mut ids = ParallelReader::new(File::open(SAMPLE)?)
+        .batch_size(7)
+        .unordered::<Body>()
+        .map(|b| b.map(|b| b.id()))
+        .collect::<Result<Vec<_>>>()?;
+    let mut expected = sequential_ids()?;
+    ids.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(ids, expected);
+    Ok(())
+}
+
+#[test]
+fn ordered_with_one_batch_in_flight() -> Result<()> {
+    let ids = ParallelReader::new(File::open(SAMPLE)?)
+        .batch_size(3)
+        .queue_depth(1)
+        .num_threads(4)
+        .ordered::<Body>()
+        .map(|b| b.map(|b| b.id()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(ids, sequential_ids()?);
+    Ok(())
+}
+
+#[test]
+fn consumer_on_global_pool() -> Result<()> {
+    let count = ParallelReader::new(File::open(SAMPLE)?)
+        .batch_size(1)
+        .queue_depth(1)
+        .unordered::<Body>()
+        .par_bridge()
+        .map(|b| b.map(|b| b.id()))
+        .collect::<Result<Vec<_>>>()?
+        .len();
+    assert_eq!(count, sequential_ids()?.len());
+    Ok(())
+}
+
+#[test]
+fn for_each_and_fold() -> Result<()> {
+    let expected = sequential_ids()?;
+
+    let count = AtomicUsize::new(0);
+    ParallelReader::open(SAMPLE)?
+        .batch_size(5)
+        .par_for_each(|_: Body| {
+            count.fetch_add(1, Ordering::Relaxed);
+        })?;
+    assert_eq!(count.into_inner(), expected.len());
+
+    let sum = ParallelReader::open(SAMPLE)?.batch_size(5).par_fold(
+        || 0u64,
+        |acc, b: Body| acc + b.id(),
+        |a, b| a + b,
+    )?;
+    assert_eq!(sum, expected.iter().sum::<u64>());
+    Ok(())
+}
+
+#[test]
+fn errors_keep_position() {
+    let input = "[\n{\"id\":1}\n]\n";
+    let results = ParallelReader::new(input.as_bytes())
+        .ordered::<Body>()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    let err = results[0].as_ref().unwrap_err();
+    assert_eq!(err.downcast_ref::<Position>().unwrap().line, 2);
+}
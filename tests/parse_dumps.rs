@@ -3,20 +3,21 @@
 //! This tests parse the full dump files.
 //!
 //! If you find failing patterns, please add them to the `sampled_json` directory.
-
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+//!
+//! The dumps are gzipped, so these tests need the `gzip` feature.
+#![cfg(feature = "gzip")]
 
 use anyhow::{Context, Result};
-use flate2::read::GzDecoder;
 use serde_json::{from_str, to_string};
 
 use edsm_dumps_model::model::body::Body;
+use edsm_dumps_model::model::codex::Codex;
 use edsm_dumps_model::model::powerplay::PowerPlay;
 use edsm_dumps_model::model::station::Station;
 use edsm_dumps_model::model::system::{SystemWithCoordinates, SystemWithoutCoordinates};
 use edsm_dumps_model::model::system_populated::SystemPopulated;
 use edsm_dumps_model::model::RootEntry;
+use edsm_dumps_model::reader::open_dump;
 
 #[test]
 #[ignore]
@@ -24,6 +25,12 @@ fn parse_bodies_7days() -> Result<()> {
     try_parse::<Body>("./dumps/bodies7days.json.gz")
 }
 
+#[test]
+#[ignore]
+fn parse_codex() -> Result<()> {
+    try_parse::<Codex>("./dumps/codex.json.gz")
+}
+
 #[test]
 #[ignore]
 fn parse_power_play() -> Result<()> {
@@ -55,38 +62,21 @@ fn parse_systems_without_coordinates() -> Result<()> {
 }
 
 fn try_parse<T: RootEntry + std::fmt::Debug + PartialEq>(path: &str) -> Result<()> {
-    let f = File::open(path).context("failed to read file")?;
-    let r = GzDecoder::new(f);
-    let r = BufReader::new(r);
-
-    for (line_num, line) in r.lines().enumerate() {
-        let line = line?;
-        let line = line.trim().trim_end_matches(',');
-
-        if line == "[" {
-            continue;
-        }
-        if line == "]" {
-            break;
-        }
-
-        try_round_trip::<T>(line).with_context(|| format!("failed at line {}", line_num + 1))?;
+    for entry in open_dump::<T>(path)? {
+        try_round_trip(&entry?)?;
     }
 
     Ok(())
 }
 
-fn try_round_trip<T: RootEntry + std::fmt::Debug + PartialEq>(line: &str) -> Result<()> {
-    let decoded = T::parse_dump_json(line.as_bytes())
-        .with_context(|| format!("parsing sample JSON: {}", line))?;
-
-    let encoded = to_string(&decoded).context("encoding decoded valuew to JSON")?;
+fn try_round_trip<T: RootEntry + std::fmt::Debug + PartialEq>(decoded: &T) -> Result<()> {
+    let encoded = to_string(decoded).context("encoding decoded valuew to JSON")?;
 
     let re_decoded: T =
         from_str(&encoded).with_context(|| format!("parsing encoded value: {}", encoded))?;
 
     assert_eq!(
-        decoded, re_decoded,
+        decoded, &re_decoded,
         "parsed value and re-parsed value should matches\nDecoded: {:?}\nRe-decoded: {:?}",
         decoded, re_decoded
     );
@@ -12,6 +12,7 @@ use anyhow::{Context, Result};
 use serde_json::{from_str, to_string};
 
 use edsm_dumps_model::model::body::Body;
+use edsm_dumps_model::model::codex::Codex;
 use edsm_dumps_model::model::powerplay::PowerPlay;
 use edsm_dumps_model::model::station::Station;
 use edsm_dumps_model::model::system::{SystemWithCoordinates, SystemWithoutCoordinates};
@@ -23,6 +24,11 @@ fn parse_body() -> Result<()> {
     try_parse::<Body>("./sampled_json/body.json")
 }
 
+#[test]
+fn parse_codex() -> Result<()> {
+    try_parse::<Codex>("./sampled_json/codex.json")
+}
+
 #[test]
 fn parse_power_play() -> Result<()> {
     try_parse::<PowerPlay>("./sampled_json/powerPlay.json")
@@ -0,0 +1,112 @@
+//! Tests for derived physical properties.
+
+use std::fs::File;
+
+use anyhow::Result;
+
+use edsm_dumps_model::model::body::{Body, BodyT};
+use edsm_dumps_model::physics::{
+    self, Kilograms, Meters, MetersPerSecondSquared, Physical, EARTH_MASS,
+};
+use edsm_dumps_model::reader::DumpReader;
+use edsm_dumps_model::system_tree::SystemTree;
+
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a / b - 1.0).abs() < tolerance
+}
+
+struct Earth;
+
+impl Physical for Earth {
+    fn mass(&self) -> Kilograms {
+        Kilograms(EARTH_MASS)
+    }
+
+    fn radius(&self) -> Meters {
+        Meters(6.371e6)
+    }
+
+    fn rotational_period(&self) -> Option<f64> {
+        Some(-0.997)
+    }
+}
+
+#[test]
+fn earth() {
+    assert!(close(Earth.density().0, 5514.0, 1e-3));
+    assert!(close(Earth.surface_gravity().0, 9.82, 1e-3));
+    assert!(close(Earth.surface_gravity().in_g(), 1.0, 1e-2));
+    assert!(close(Earth.escape_velocity().in_km_per_s(), 11.186, 1e-3));
+    assert!(close(Earth.surface_area().0, 5.1007e14, 1e-3));
+    assert!(close(Earth.equatorial_speed().unwrap().0, 464.7, 1e-3));
+}
+
+#[test]
+fn units() {
+    assert!(close(Meters::from_au(1.0).in_ls(), 499.005, 1e-5));
+    assert_eq!(
+        format!("{:.2}", MetersPerSecondSquared(9.80665)),
+        "9.81 m/s²"
+    );
+    assert_eq!(Meters(1500.0).to_string(), "1500 m");
+}
+
+fn bodies() -> Result<Vec<Body>> {
+    DumpReader::<Body, _>::new(File::open("./sampled_json/body.json")?).collect()
+}
+
+#[test]
+fn gravity_agrees_with_edsm() -> Result<()> {
+    let mut checked = 0;
+    for body in bodies()? {
+        if let Body::Planet(planet) = body {
+            if let Some(d) = physics::gravity_discrepancy(&planet) {
+                assert!(d.abs() < 0.01, "{}: {}", planet.name, d);
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked > 0);
+    Ok(())
+}
+
+#[test]
+fn stars() -> Result<()> {
+    for body in bodies()? {
+        if let Body::Star(star) = body {
+            if star.solar_radius == 0.0 || star.surface_temperature == 0 {
+                // Incomplete records.
+                continue;
+            }
+            let l = physics::luminosity(&star).in_solar_luminosities();
+            assert!(l > 0.0, "{}", star.name);
+            assert!(star.density().0 > 0.0);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn hill_sphere_and_roche_limit() -> Result<()> {
+    // Byooe Thaa QS-S c4-0
+    let tree = SystemTree::new(
+        bodies()?
+            .into_iter()
+            .filter(|b| b.system_id64() == Some(106065827114)),
+    );
+    for node in tree.nodes() {
+        let Some(Body::Planet(planet)) = &node.body else {
+            continue;
+        };
+        let hill = physics::hill_sphere(&tree, node.body_id).unwrap();
+        let roche = physics::roche_limit(&tree, node.body_id).unwrap();
+        assert!(hill.0 > planet.radius().0, "{}", planet.name);
+        let a = physics::Meters::from_au(planet.semi_major_axis.unwrap() as f64);
+        assert!(roche < a, "{}", planet.name);
+    }
+    // Stars orbiting the root barycentre.
+    assert!(physics::hill_sphere(&tree, 1).is_some());
+    assert!(physics::node_mass(&tree, 0).unwrap() > physics::node_mass(&tree, 1).unwrap());
+    assert_eq!(physics::hill_sphere(&tree, 0), None);
+    Ok(())
+}
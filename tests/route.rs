@@ -0,0 +1,221 @@
+//! Tests for route planning.
+
+use std::fs::File;
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+
+use edsm_dumps_model::model::body::{Body, Star, StarSubType};
+use edsm_dumps_model::model::system::{Coords, SystemWithCoordinates};
+use edsm_dumps_model::reader::DumpReader;
+use edsm_dumps_model::route::carrier::CarrierHop;
+use edsm_dumps_model::route::{
+    Boost, BoostMap, Carrier, CarrierPlanner, FuelModel, Optimize, Route, RoutePlanner, StarMap,
+};
+
+fn system(id: u64, name: &str, x: f32, y: f32) -> SystemWithCoordinates {
+    SystemWithCoordinates {
+        id,
+        coords: Coords { x, y, z: 0.0 },
+        id64: None,
+        name: name.to_owned(),
+        date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    }
+}
+
+/// A short detour with fewer jumps next to a straight line of shorter jumps.
+fn map() -> StarMap {
+    StarMap::new(vec![
+        system(1, "Start", 0.0, 0.0),
+        system(2, "Line 1", 20.0 / 3.0, 0.0),
+        system(3, "Line 2", 40.0 / 3.0, 0.0),
+        system(4, "Goal", 20.0, 0.0),
+        system(5, "Detour", 10.0, 1.0),
+        system(6, "Far Away", 500.0, 0.0),
+    ])
+}
+
+fn names(route: &Route) -> Vec<&str> {
+    route.hops.iter().map(|h| h.system.name.as_str()).collect()
+}
+
+#[test]
+fn minimize_jumps_or_distance() -> Result<()> {
+    let map = map();
+
+    let route = RoutePlanner::new(&map, 10.5).plan("Start", "goal")?;
+    assert_eq!(names(&route), ["Start", "Detour", "Goal"]);
+    assert_eq!(route.jumps(), 2);
+
+    let route = RoutePlanner::new(&map, 10.5)
+        .optimize(Optimize::Distance)
+        .plan("Start", "Goal")?;
+    assert_eq!(names(&route), ["Start", "Line 1", "Line 2", "Goal"]);
+    assert!((route.total_distance() - 20.0).abs() < 1e-4);
+    assert_eq!(route.total_fuel(), None);
+    Ok(())
+}
+
+#[test]
+fn coords_snap_to_nearest_system() -> Result<()> {
+    let map = map();
+    let from = Coords {
+        x: -1.0,
+        y: 0.5,
+        z: 0.0,
+    };
+    let to = Coords {
+        x: 19.0,
+        y: 0.0,
+        z: 1.0,
+    };
+    let route = RoutePlanner::new(&map, 25.0).plan(from, to)?;
+    assert_eq!(names(&route), ["Start", "Goal"]);
+    Ok(())
+}
+
+#[test]
+fn names_ignore_case() {
+    let map = map();
+    assert_eq!(map.get("far away").map(|s| s.id), Some(6));
+    assert_eq!(map.get("LINE 2").map(|s| s.id), Some(3));
+    assert!(map.get("Line").is_none());
+}
+
+#[test]
+fn unreachable_or_unknown() {
+    let map = map();
+    assert!(RoutePlanner::new(&map, 10.5)
+        .plan("Start", "Far Away")
+        .is_err());
+    assert!(RoutePlanner::new(&map, 10.5)
+        .plan("Start", "Nowhere")
+        .is_err());
+}
+
+#[test]
+fn fuel_model_limits_range() -> Result<()> {
+    let fuel = FuelModel {
+        mass: 120.0,
+        optimal_mass: 100.0,
+        max_fuel_per_jump: 1.0,
+        linear_constant: 12.0,
+        power_constant: 2.0,
+    };
+    assert!((fuel.fuel_for(fuel.max_range()) - 1.0).abs() < 1e-4);
+    assert!(fuel.max_range() < 10.0);
+
+    let map = map();
+    let planner = RoutePlanner::new(&map, 50.0).fuel(fuel);
+    assert_eq!(planner.effective_range(), fuel.max_range());
+
+    let route = planner.plan("Start", "Goal")?;
+    assert_eq!(names(&route), ["Start", "Line 1", "Line 2", "Goal"]);
+    let total = route.total_fuel().unwrap();
+    assert!((total - 3.0 * fuel.fuel_for(20.0 / 3.0)).abs() < 1e-4);
+    Ok(())
+}
+
+fn star(system_id64: u64, sub_type: StarSubType, is_main_star: bool) -> Result<Body> {
+    let path = "./sampled_json/body.json";
+    for body in DumpReader::<Body, _>::new(File::open(path)?) {
+        if let Body::Star(star) = body? {
+            return Ok(Body::Star(Star {
+                id64: None,
+                system_id64: Some(system_id64),
+                sub_type,
+                is_main_star,
+                ..star
+            }));
+        }
+    }
+    panic!("no star in {}", path);
+}
+
+#[test]
+fn boosted_route() -> Result<()> {
+    let mut systems = vec![
+        system(1, "Start", 0.0, 0.0),
+        system(2, "Neutron", 10.0, 0.0),
+        system(3, "Dwarf", 50.0, 0.0),
+        system(4, "Goal", 65.0, 0.0),
+    ];
+    for (i, s) in systems.iter_mut().enumerate() {
+        s.id64 = Some(100 + i as u64);
+    }
+    let map = StarMap::new(systems);
+    assert!(RoutePlanner::new(&map, 11.0).plan("Start", "Goal").is_err());
+
+    let mut boosts = BoostMap::new();
+    assert!(boosts.add(&star(101, StarSubType::NeutronStar, false)?));
+    assert!(boosts.add(&star(102, StarSubType::WhiteDwarfDAStar, true)?));
+    assert!(!boosts.add(&star(103, StarSubType::GWhiteYellowStar, true)?));
+    assert_eq!(boosts.get(101), Some(Boost::Neutron));
+    assert_eq!(boosts.max_factor(), 4.0);
+
+    let route = RoutePlanner::new(&map, 11.0)
+        .boosts(&boosts)
+        .plan("Start", "Goal")?;
+    assert_eq!(names(&route), ["Start", "Neutron", "Dwarf", "Goal"]);
+    let used: Vec<_> = route.hops.iter().map(|h| h.boost).collect();
+    assert_eq!(
+        used,
+        [None, None, Some(Boost::Neutron), Some(Boost::WhiteDwarf)]
+    );
+    assert_eq!(route.boosted_jumps(), 2);
+
+    let mut main_only = BoostMap::main_star_only();
+    assert!(!main_only.add(&star(101, StarSubType::NeutronStar, false)?));
+    assert!(RoutePlanner::new(&map, 11.0)
+        .boosts(&main_only)
+        .plan("Start", "Goal")
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn carrier_route() -> Result<()> {
+    let map = StarMap::new(vec![
+        system(1, "Start", 0.0, 0.0),
+        system(2, "Halfway", 450.0, 0.0),
+        system(3, "Goal", 900.0, 0.0),
+        system(4, "Beyond", 1500.0, 0.0),
+    ]);
+    let carrier = Carrier {
+        used_capacity: 5000,
+        fuel: 50,
+        reserve: 1000,
+    };
+    // 5 + 500 * (25000 + 6050) / 200000 = 82.625
+    assert_eq!(carrier.jump_cost(500.0), 83);
+
+    let route = CarrierPlanner::new(&map, carrier).plan("Start", "Goal")?;
+    assert_eq!(names_of(&route.hops), ["Start", "Halfway", "Goal"]);
+
+    let first = carrier.jump_cost(450.0);
+    assert_eq!(route.hops[1].tritium, first);
+    // The tank is refilled from the reserve before the first jump.
+    assert_eq!(route.hops[1].carrier.reserve, 50);
+    assert_eq!(route.hops[1].carrier.fuel, 1000 - first);
+    assert_eq!(route.total_tritium(), first + route.hops[2].tritium);
+    assert!(route.hops[2].tritium <= first);
+
+    let dry = Carrier {
+        fuel: 50,
+        reserve: 0,
+        ..carrier
+    };
+    let err = CarrierPlanner::new(&map, dry)
+        .plan("Start", "Goal")
+        .unwrap_err();
+    assert_eq!(err.to_string(), "jumping to Halfway");
+    assert!(CarrierPlanner::new(&map, carrier)
+        .jump_range(400.0)
+        .plan("Start", "Goal")
+        .is_err());
+    Ok(())
+}
+
+fn names_of(hops: &[CarrierHop]) -> Vec<&str> {
+    hops.iter().map(|h| h.system.name.as_str()).collect()
+}
@@ -0,0 +1,98 @@
+//! Tests for schema drift reports.
+
+use std::fs::{self, File};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use edsm_dumps_model::model::body::Body;
+use edsm_dumps_model::model::station::Station;
+use edsm_dumps_model::model::system::SystemWithCoordinates;
+use edsm_dumps_model::schema_report::schema_report;
+
+#[test]
+fn sampled_bodies_are_clean() -> Result<()> {
+    let report = schema_report::<Body, _>(File::open("./sampled_json/body.json")?)?;
+    assert!(report.is_clean(), "{}", report);
+    assert!(report.entries > 0);
+    Ok(())
+}
+
+#[test]
+fn drifted_bodies() -> Result<()> {
+    let s = fs::read_to_string("./sampled_json/body.json")?;
+    let mut lines = Vec::new();
+    for (i, line) in s.lines().skip(1).take(4).enumerate() {
+        let mut v: Value = serde_json::from_str(line.trim().trim_end_matches(','))?;
+        if v["type"] != "Planet" {
+            continue;
+        }
+        v["newField"] = json!(i);
+        v["atmosphereType"] = json!("Thin Xenon");
+        if i == 0 {
+            v["subType"] = json!("Crystal world");
+        }
+        lines.push(serde_json::to_string(&v)?);
+    }
+    let dump = format!("[\n{}\n]\n", lines.join(",\n"));
+
+    let report = schema_report::<Body, _>(dump.as_bytes())?;
+    let n = lines.len() as u64;
+    assert!(n >= 2);
+    assert_eq!(report.entries, n);
+    assert_eq!(report.drifted_entries, n);
+    assert!(report.failures.is_none(), "{}", report);
+
+    let field = &report.unknown_fields["newField"];
+    assert_eq!(field.count, n);
+    assert_eq!(field.first.position.line, 2);
+    assert_eq!(field.first.line, lines[0]);
+
+    assert_eq!(
+        report.unknown_variants["AtmosphereType"]["Thin Xenon"].count,
+        n
+    );
+    assert_eq!(
+        report.unknown_variants["PlanetSubType"]["Crystal world"].count,
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn failures_are_counted() -> Result<()> {
+    let dump = r#"[
+    {"coords":{"x":1,"y":2,"z":3},"date":"2015-05-12 15:29:33","id":1,"id64":2,"name":"A","extra":[1]},
+    {"coords":{"x":1,"y":2},"date":"2015-05-12 15:29:33","id":2,"id64":3,"name":"B"}
+]"#;
+    let report = schema_report::<SystemWithCoordinates, _>(dump.as_bytes())?;
+    assert_eq!(report.entries, 2);
+    assert_eq!(report.drifted_entries, 1);
+    assert_eq!(report.unknown_fields["extra"].count, 1);
+
+    let failures = report.failures.as_ref().unwrap();
+    assert_eq!(failures.count, 1);
+    assert_eq!(failures.first.position.line, 3);
+    assert!(!report.is_clean());
+    Ok(())
+}
+
+#[test]
+fn failing_entries_are_walked() -> Result<()> {
+    // `haveMarket` fails to parse before any of the drift is reached.
+    let dump = r#"[
+    {"id":1,"haveMarket":"yes","haveOutfitting":false,"haveShipyard":false,"name":"A","otherServices":["Holo-Me"],"type":"Dockable wreck","ships":[{"id":1,"name":"Sidewinder","price":0}],"updateTime":{"information":"2024-01-01 00:00:00","fleet":null},"extra":1}
+]"#;
+    let report = schema_report::<Station, _>(dump.as_bytes())?;
+    assert_eq!(report.failures.as_ref().map(|f| f.count), Some(1));
+    assert_eq!(report.drifted_entries, 1);
+
+    let fields: Vec<_> = report.unknown_fields.keys().map(String::as_str).collect();
+    assert_eq!(fields, ["extra", "ships[].price", "updateTime.fleet"]);
+    assert_eq!(report.unknown_variants["OtherService"]["Holo-Me"].count, 1);
+    assert_eq!(
+        report.unknown_variants["StationType"]["Dockable wreck"].count,
+        1
+    );
+    Ok(())
+}
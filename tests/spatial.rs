@@ -0,0 +1,131 @@
+//! Tests for the spatial index.
+
+use std::fs::File;
+
+use anyhow::Result;
+
+use edsm_dumps_model::model::system::{Coords, SystemWithCoordinates};
+use edsm_dumps_model::reader::DumpReader;
+use edsm_dumps_model::spatial::{Point, SpatialIndex};
+
+fn grid() -> Vec<Point> {
+    let mut points = Vec::new();
+    for x in 0..10 {
+        for y in 0..10 {
+            for z in 0..10 {
+                points.push(Point {
+                    id: x * 100 + y * 10 + z,
+                    coords: Coords {
+                        x: x as f32 * 1.5,
+                        y: y as f32 * -2.0,
+                        z: z as f32 + 0.25 * x as f32,
+                    },
+                });
+            }
+        }
+    }
+    points
+}
+
+fn brute_force(points: &[Point], center: Coords) -> Vec<(f32, u64)> {
+    let mut all: Vec<_> = points
+        .iter()
+        .map(|p| (p.coords.dist(center), p.id))
+        .collect();
+    all.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    all
+}
+
+fn center() -> Coords {
+    Coords {
+        x: 6.2,
+        y: -9.1,
+        z: 4.4,
+    }
+}
+
+#[test]
+fn nearest_matches_brute_force() {
+    let points = grid();
+    let index = SpatialIndex::new(points.clone());
+    let expected = brute_force(&points, center());
+
+    for k in [0, 1, 7, 50, 2000] {
+        let found: Vec<_> = index
+            .nearest(center(), k)
+            .iter()
+            .map(|n| (n.dist, n.id))
+            .collect();
+        assert_eq!(found, expected[..k.min(points.len())]);
+    }
+}
+
+#[test]
+fn radius_matches_brute_force() {
+    let points = grid();
+    let index = SpatialIndex::new(points.clone());
+    let expected: Vec<_> = brute_force(&points, center())
+        .into_iter()
+        .filter(|(d, _)| *d <= 4.0)
+        .collect();
+
+    let found: Vec<_> = index
+        .within_radius(center(), 4.0)
+        .iter()
+        .map(|n| (n.dist, n.id))
+        .collect();
+    assert_eq!(found, expected);
+
+    let mut visited = Vec::new();
+    index.for_each_within(center(), 4.0, |n| visited.push((n.dist, n.id)));
+    visited.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    assert_eq!(visited, expected);
+}
+
+#[test]
+fn box_matches_brute_force() {
+    let points = grid();
+    let index = SpatialIndex::new(points.clone());
+    let min = Coords {
+        x: 1.5,
+        y: -9.0,
+        z: 2.0,
+    };
+    let max = Coords {
+        x: 7.5,
+        y: -2.0,
+        z: 6.0,
+    };
+    let inside = |c: Coords| {
+        (min.x..=max.x).contains(&c.x)
+            && (min.y..=max.y).contains(&c.y)
+            && (min.z..=max.z).contains(&c.z)
+    };
+
+    let mut found: Vec<_> = index.within_box(min, max).iter().map(|p| p.id).collect();
+    found.sort_unstable();
+    let expected: Vec<_> = points
+        .iter()
+        .filter(|p| inside(p.coords))
+        .map(|p| p.id)
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn round_trip_through_bytes() -> Result<()> {
+    let path = "./sampled_json/systemWithCoordinates.json";
+    let index = SpatialIndex::from_entries(
+        DumpReader::<SystemWithCoordinates, _>::new(File::open(path)?)
+            .collect::<Result<Vec<_>>>()?,
+    );
+    assert!(!index.is_empty());
+
+    let mut bs = Vec::new();
+    index.write_to(&mut bs)?;
+    assert_eq!(SpatialIndex::read_from(&bs[..])?, index);
+    assert!(SpatialIndex::read_from(&bs[..bs.len() - 1]).is_err());
+    assert!(SpatialIndex::read_from(&b"not an index"[..]).is_err());
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Tests for stellar classifications.
+
+use std::fs::File;
+
+use anyhow::Result;
+use strum::IntoEnumIterator;
+
+use edsm_dumps_model::model::body::{
+    Body, Luminosity, SpectralClass, StarClass, StarSubType, StellarClassification,
+};
+use edsm_dumps_model::reader::DumpReader;
+
+#[test]
+fn sample_classifications_are_consistent() -> Result<()> {
+    let mut classified = 0;
+    for body in DumpReader::<Body, _>::new(File::open("./sampled_json/body.json")?) {
+        if let Body::Star(star) = body? {
+            star.validate_classification()?;
+            if let Some(c) = star.classification() {
+                assert_eq!(c.to_string().parse::<_>().ok(), Some(c));
+                classified += 1;
+            }
+        }
+    }
+    assert!(classified > 0);
+    Ok(())
+}
+
+#[test]
+fn spectral_class_parts() {
+    for c in SpectralClass::iter() {
+        let letter = c.letter().unwrap();
+        if letter != StarClass::ProtoStars {
+            assert_eq!(SpectralClass::from_parts(letter, c.subclass()), Some(c));
+        }
+    }
+    assert_eq!(SpectralClass::K5.letter(), Some(StarClass::KTypeStars));
+    assert_eq!(SpectralClass::K5.subclass(), Some(5));
+    assert_eq!(SpectralClass::M.subclass(), None);
+    assert_eq!(SpectralClass::TTS3.letter(), Some(StarClass::ProtoStars));
+    assert_eq!(SpectralClass::AeBe9.subclass(), Some(9));
+    assert_eq!(SpectralClass::T0.letter(), Some(StarClass::TTypeStars));
+    assert_eq!(SpectralClass::Other("X1".to_owned()).letter(), None);
+}
+
+#[test]
+fn stellar_classification() {
+    let k5: StellarClassification = "K5 Vab".parse().unwrap();
+    assert_eq!(k5.class, SpectralClass::K5);
+    assert_eq!(k5.luminosity, Some(Luminosity::Vab));
+    assert_eq!(k5.to_string(), "K5 Vab");
+    assert_eq!(
+        "G".parse::<StellarClassification>().unwrap().to_string(),
+        "G"
+    );
+    assert!("K5 Vab X".parse::<StellarClassification>().is_err());
+    assert!("Q5".parse::<StellarClassification>().is_err());
+    assert!("K5 X".parse::<StellarClassification>().is_err());
+
+    let mut sorted: Vec<StellarClassification> = [
+        "G2 V", "O9", "K", "K4", "K5", "M0", "TTS1", "B0", "AeBe3", "F0",
+    ]
+    .iter()
+    .map(|s| s.parse().unwrap())
+    .collect();
+    sorted.sort();
+    let sorted: Vec<_> = sorted.iter().map(|c| c.to_string()).collect();
+    // Proto stars rank with main sequence classes of similar temperature.
+    assert_eq!(
+        sorted,
+        ["M0", "K5", "K", "K4", "TTS1", "G2 V", "F0", "AeBe3", "B0", "O9"]
+    );
+
+    assert!(k5.is_consistent_with(&StarSubType::KYellowOrangeGiantStar));
+    assert!(!k5.is_consistent_with(&StarSubType::MRedDwarfStar));
+}
@@ -0,0 +1,84 @@
+//! Tests for system trees.
+
+use std::fs::File;
+
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+
+use edsm_dumps_model::model::body::{Body, BodyT};
+use edsm_dumps_model::orbit::{self, Orbit};
+use edsm_dumps_model::reader::DumpReader;
+use edsm_dumps_model::system_tree::{NodeKind, SystemTree};
+
+fn system_bodies(system_id64: u64) -> Result<Vec<Body>> {
+    let bodies = DumpReader::<Body, _>::new(File::open("./sampled_json/body.json")?)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(bodies
+        .into_iter()
+        .filter(|b| b.system_id64() == Some(system_id64))
+        .collect())
+}
+
+#[test]
+fn binary_system() -> Result<()> {
+    // Byooe Thaa QS-S c4-0
+    let tree = SystemTree::new(system_bodies(106065827114)?);
+
+    let roots: Vec<_> = tree.roots().map(|n| n.body_id).collect();
+    assert_eq!(roots, [0]);
+    assert_eq!(tree.get(0).unwrap().kind, NodeKind::Barycentre);
+    assert_eq!(tree.get(0).unwrap().children, [1, 2]);
+    assert_eq!(tree.get(1).unwrap().children, [3, 4, 7]);
+    assert_eq!(tree.depth(17), Some(2));
+    assert_eq!(tree.ancestors(17), Some(vec![2, 0]));
+    assert_eq!(
+        tree.parent(3).unwrap().name(),
+        Some("Byooe Thaa QS-S c4-0 A")
+    );
+    assert_eq!(tree.dangling().count(), 0);
+
+    let expected = "\
+Barycentre 0
+├── Byooe Thaa QS-S c4-0 A (star 1)
+│   ├── Byooe Thaa QS-S c4-0 A 1 (planet 3)
+│   ├── Byooe Thaa QS-S c4-0 A 2 (planet 4)
+│   └── Byooe Thaa QS-S c4-0 A 5 (planet 7)
+└── Byooe Thaa QS-S c4-0 B (star 2)
+    ├── Byooe Thaa QS-S c4-0 B 1 (planet 16)
+    └── Byooe Thaa QS-S c4-0 B 2 (planet 17)
+";
+    assert_eq!(tree.to_string(), expected);
+    Ok(())
+}
+
+#[test]
+fn dangling_parents() -> Result<()> {
+    // Hypheerld IY-H b42-5
+    let tree = SystemTree::new(system_bodies(11614798552937)?);
+
+    let dangling: Vec<_> = tree.dangling().map(|n| (n.body_id, n.kind)).collect();
+    assert_eq!(dangling, [(2, NodeKind::Star), (16, NodeKind::Planet)]);
+    assert_eq!(tree.depth(19), Some(3));
+    assert!(tree.to_string().contains("Missing planet 16"));
+    assert!(tree.unplaced().is_empty());
+    Ok(())
+}
+
+#[test]
+fn distances_in_tree() -> Result<()> {
+    let tree = SystemTree::new(system_bodies(106065827114)?);
+    let t = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+
+    // Planets of star A orbit it directly.
+    let a1 = Orbit::from_body(tree.get(3).unwrap().body.as_ref().unwrap()).unwrap();
+    let a1_to_a = orbit::distance(&tree, 3, 1, t).unwrap();
+    assert!((a1_to_a - a1.distance_at(t)).abs() < 1e-9);
+    assert!(a1.periapsis() <= a1_to_a + 1e-9 && a1_to_a <= a1.apoapsis() + 1e-9);
+
+    // A planet of star B is measured through the barycentre.
+    let b1_to_a1 = orbit::distance(&tree, 16, 3, t).unwrap();
+    let via = orbit::position(&tree, 16, t).unwrap() - orbit::position(&tree, 3, t).unwrap();
+    assert!((b1_to_a1 - via.abs()).abs() < 1e-9);
+    assert_eq!(orbit::distance(&tree, 3, 999, t), None);
+    Ok(())
+}
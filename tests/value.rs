@@ -0,0 +1,51 @@
+//! Tests for exploration data values.
+
+use edsm_dumps_model::model::body::{PlanetSubType, StarSubType};
+use edsm_dumps_model::value::{planet_value_of, star_value_of, Scan};
+
+#[test]
+fn earth_like_world() {
+    let elw = |scan| planet_value_of(PlanetSubType::EarthLikeWorld, false, 1.0, scan);
+    // (64831 + 116295) * (1 + 0.56591828)
+    assert_eq!(elw(Scan::fss()), 283629);
+    // 283628.5 * 10/3 * 1.3 * 1.25
+    assert_eq!(elw(Scan::mapped()), 1536321);
+    assert!(elw(Scan::mapped().first()) > elw(Scan::mapped()));
+}
+
+#[test]
+fn terraformable_bonus() {
+    let hmc = |tf| planet_value_of(PlanetSubType::HighMetalContentWorld, tf, 0.5, Scan::fss());
+    assert!(hmc(true) > 10 * hmc(false));
+    let icy = |tf| planet_value_of(PlanetSubType::IcyBody, tf, 0.5, Scan::fss());
+    assert_eq!(icy(false), 500);
+    assert_eq!(
+        planet_value_of(PlanetSubType::IcyBody, false, 0.5, Scan::fss().first()),
+        1300
+    );
+}
+
+#[test]
+fn terraformable_metal_rich_body() {
+    let mrb = |tf| planet_value_of(PlanetSubType::MetalRichBody, tf, 1.0, Scan::fss());
+    // 21790 * (1 + 0.56591828)
+    assert_eq!(mrb(false), 34121);
+    // (21790 + 65631) * (1 + 0.56591828)
+    assert_eq!(mrb(true), 136894);
+}
+
+#[test]
+fn stars() {
+    assert_eq!(
+        star_value_of(StarSubType::GWhiteYellowStar, 1.0, Scan::fss()),
+        1218
+    );
+    assert_eq!(
+        star_value_of(StarSubType::NeutronStar, 1.0, Scan::fss()),
+        22970
+    );
+    assert_eq!(
+        star_value_of(StarSubType::WhiteDwarfDAStar, 0.0, Scan::fss()),
+        14057
+    );
+}
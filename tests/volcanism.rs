@@ -0,0 +1,41 @@
+//! Tests for volcanism decomposition and filters.
+
+use edsm_dumps_model::model::body::{
+    VolcanismFilter, VolcanismIntensity, VolcanismKind, VolcanismSubstance, VolcanismType,
+};
+
+#[test]
+fn volcanism_type_decomposition() {
+    for t in VolcanismType::active() {
+        let v = t.volcanism().unwrap();
+        assert_eq!(VolcanismType::from_volcanism(v), Some(t.clone()));
+        let name = t.to_string();
+        assert!(name.contains(&v.substance.to_string()));
+        assert!(name.ends_with(&v.kind.to_string()));
+        let prefix = match v.intensity {
+            VolcanismIntensity::Normal => {
+                !name.starts_with("Minor ") && !name.starts_with("Major ")
+            }
+            i => name.starts_with(&format!("{} ", i)),
+        };
+        assert!(prefix, "{}", name);
+    }
+    assert_eq!(VolcanismType::NoVolcanism.volcanism(), None);
+
+    let water_geysers = VolcanismFilter {
+        kind: Some(VolcanismKind::Geysers),
+        substance: Some(VolcanismSubstance::Water),
+        ..VolcanismFilter::default()
+    };
+    let types: Vec<_> = water_geysers.types().collect();
+    assert_eq!(
+        types,
+        [
+            VolcanismType::MajorWaterGeysers,
+            VolcanismType::MinorWaterGeysers,
+            VolcanismType::WaterGeysers
+        ]
+    );
+    assert!(!water_geysers.matches(None));
+    assert!(!VolcanismFilter::default().matches(Some(&VolcanismType::NoVolcanism)));
+}